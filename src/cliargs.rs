@@ -1,20 +1,50 @@
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{ArgGroup, CommandFactory, Parser};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(group(ArgGroup::new("stage").args(["lex", "parse", "tacky", "codegen", "assembly", "object"])))]
 pub struct Args {
-    /// Input source file
-    pub input: String,
-    /// Output file
-    #[arg(short, long, default_value = "a.out")]
-    pub output: String,
+    /// Input source files. Files other than C sources are passed on to
+    /// the linker as they are
+    #[arg(required = true)]
+    pub input: Vec<String>,
+    /// Output file (defaults to the first input file name without its extension)
+    #[arg(short, long)]
+    pub output: Option<String>,
     /// Run scanner but do not parse tokens
     #[arg(short, long)]
     pub lex: bool,
     /// Run parser but do not generate code
     #[arg(short, long)]
     pub parse: bool,
-    /// Generate code
-    #[arg(short, long)]
+    /// Generate the intermediate representation but not assembly
+    #[arg(long)]
+    pub tacky: bool,
+    /// Generate assembly but do not emit it
+    #[arg(long)]
     pub codegen: bool,
+    /// Emit an assembly file but do not assemble or link it
+    #[arg(short = 'S')]
+    pub assembly: bool,
+    /// Compile and assemble but do not link
+    #[arg(short = 'c')]
+    pub object: bool,
+}
+
+impl Args {
+    /// Parse the command line, exiting with a usage error if the
+    /// combination of options is not valid
+    pub fn parse_and_validate() -> Self {
+        let args = Self::parse();
+        if args.output.is_some() && args.input.len() > 1 && (args.assembly || args.object) {
+            Self::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "cannot specify '--output' with '-S' or '-c' and multiple input files",
+                )
+                .exit();
+        }
+        args
+    }
 }
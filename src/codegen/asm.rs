@@ -0,0 +1,190 @@
+//! Assembly AST for x86-64, lowered from the IR and written out by the
+//! emitter
+
+use crate::semantic::symbols::StaticInit;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub top_level: Vec<TopLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopLevel {
+    Function(Function),
+    StaticVariable(StaticVariable),
+    StaticConstant(StaticConstant),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub global: bool,
+    pub instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticVariable {
+    pub name: String,
+    pub global: bool,
+    pub alignment: i32,
+    pub init: Vec<StaticInit>,
+}
+
+/// A read-only value, such as a floating-point constant, that
+/// instructions refer to by its local label
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticConstant {
+    pub name: String,
+    pub alignment: i32,
+    pub init: StaticInit,
+}
+
+/// The size of the operands of an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssemblyType {
+    /// One byte
+    Byte,
+    /// Four bytes
+    Longword,
+    /// Eight bytes
+    Quadword,
+    /// A double-precision floating-point value in an XMM register or
+    /// in memory
+    Double,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    Mov(AssemblyType, Operand, Operand),
+    /// Sign extend an operand of the first type into one of the second,
+    /// which is larger
+    Movsx(AssemblyType, AssemblyType, Operand, Operand),
+    /// Zero extend a byte into an operand of the given type
+    MovZeroExtend(AssemblyType, AssemblyType, Operand, Operand),
+    Unary(UnaryOperator, AssemblyType, Operand),
+    Binary(BinaryOperator, AssemblyType, Operand, Operand),
+    Cmp(AssemblyType, Operand, Operand),
+    Idiv(AssemblyType, Operand),
+    /// Unsigned division of DX:AX
+    Div(AssemblyType, Operand),
+    /// Sign extend the accumulator into DX, ahead of a division
+    Cdq(AssemblyType),
+    /// Convert a double to a signed integer of the given size,
+    /// truncating towards zero
+    Cvttsd2si(AssemblyType, Operand, Operand),
+    /// Convert a signed integer of the given size to a double
+    Cvtsi2sd(AssemblyType, Operand, Operand),
+    /// Load the address of a memory operand
+    Lea(Operand, Operand),
+    Jmp(String),
+    JmpCC(CondCode, String),
+    SetCC(CondCode, Operand),
+    Label(String),
+    AllocateStack(i32),
+    DeallocateStack(i32),
+    Push(Operand),
+    /// Call a function. Functions that are not defined in this
+    /// translation unit are called through the procedure linkage table.
+    Call {
+        name: String,
+        plt: bool,
+    },
+    Ret,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Sub,
+    Mult,
+    And,
+    Or,
+    Xor,
+    Sal,
+    Sar,
+    Shr,
+    /// Floating-point division; integer division has its own
+    /// instructions
+    DivDouble,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Imm(i64),
+    Reg(Reg),
+    /// A variable that has not been assigned a stack slot yet
+    Pseudo(String),
+    /// A location at a byte offset into an array that has not been
+    /// assigned a stack slot yet
+    PseudoMem(String, i32),
+    /// A location relative to the frame pointer
+    Stack(i32),
+    /// A location relative to the address held in a register
+    Memory(Reg, i32),
+    /// The location at `base + index * scale`, where the scale is 1, 2,
+    /// 4 or 8
+    Indexed(Reg, Reg, i32),
+    /// A variable with static storage duration, addressed relative to
+    /// the instruction pointer
+    Data(String),
+}
+
+impl Operand {
+    pub fn is_memory(&self) -> bool {
+        matches!(
+            self,
+            Operand::Stack(_)
+                | Operand::PseudoMem(..)
+                | Operand::Memory(..)
+                | Operand::Indexed(..)
+                | Operand::Data(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reg {
+    AX,
+    CX,
+    DX,
+    DI,
+    SI,
+    R8,
+    R9,
+    R10,
+    R11,
+    XMM0,
+    XMM1,
+    XMM2,
+    XMM3,
+    XMM4,
+    XMM5,
+    XMM6,
+    XMM7,
+    XMM14,
+    XMM15,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CondCode {
+    E,
+    NE,
+    L,
+    LE,
+    G,
+    GE,
+    /// Unsigned comparisons: above and below
+    A,
+    AE,
+    B,
+    BE,
+    /// Parity flag, set when a floating-point comparison is unordered
+    P,
+    NP,
+}
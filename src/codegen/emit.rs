@@ -0,0 +1,371 @@
+//! Assembly is written in AT&T syntax for the GNU assembler on Linux
+
+use std::fmt;
+
+use crate::codegen::asm::*;
+use crate::semantic::symbols::StaticInit;
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in &self.top_level {
+            match item {
+                TopLevel::Function(function) => write!(f, "{}", function)?,
+                TopLevel::StaticVariable(var) => write!(f, "{}", var)?,
+                TopLevel::StaticConstant(constant) => write!(f, "{}", constant)?,
+            }
+        }
+        // Mark the stack as non-executable
+        writeln!(f, "\t.section\t.note.GNU-stack,\"\",@progbits")
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.global {
+            writeln!(f, "\t.globl\t{}", self.name)?;
+        }
+        writeln!(f, "\t.text")?;
+        writeln!(f, "{}:", self.name)?;
+        writeln!(f, "\tpushq\t%rbp")?;
+        writeln!(f, "\tmovq\t%rsp, %rbp")?;
+        for instruction in &self.instructions {
+            writeln!(f, "{}", instruction)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl fmt::Display for StaticVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.global {
+            writeln!(f, "\t.globl\t{}", self.name)?;
+        }
+        // Zero-initialized variables take no space in the object file
+        if self.init.iter().all(StaticInit::is_zero) {
+            writeln!(f, "\t.bss")?;
+        } else {
+            writeln!(f, "\t.data")?;
+        }
+        writeln!(f, "\t.balign\t{}", self.alignment)?;
+        writeln!(f, "{}:", self.name)?;
+        for init in &self.init {
+            writeln!(f, "{}", Init(init))?;
+        }
+        writeln!(f)
+    }
+}
+
+impl fmt::Display for StaticConstant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\t.section\t.rodata")?;
+        writeln!(f, "\t.balign\t{}", self.alignment)?;
+        writeln!(f, "{}:", self.name)?;
+        writeln!(f, "{}", Init(&self.init))?;
+        // Pad doubles that are read as a whole sixteen bytes
+        if self.alignment > 8 && matches!(self.init, StaticInit::Double(_)) {
+            writeln!(f, "\t.zero\t{}", self.alignment - 8)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// The directive that defines a static value. Doubles are written as
+/// their bits, so that the value is exact.
+struct Init<'a>(&'a StaticInit);
+
+impl fmt::Display for Init<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            StaticInit::Char(0) | StaticInit::UChar(0) => write!(f, "\t.zero\t1"),
+            StaticInit::Int(0) | StaticInit::UInt(0) => write!(f, "\t.zero\t4"),
+            StaticInit::Long(0) | StaticInit::ULong(0) => write!(f, "\t.zero\t8"),
+            StaticInit::Char(value) => write!(f, "\t.byte\t{}", value),
+            StaticInit::UChar(value) => write!(f, "\t.byte\t{}", value),
+            StaticInit::Int(value) => write!(f, "\t.long\t{}", value),
+            StaticInit::Long(value) => write!(f, "\t.quad\t{}", value),
+            StaticInit::UInt(value) => write!(f, "\t.long\t{}", value),
+            StaticInit::ULong(value) => write!(f, "\t.quad\t{}", value),
+            StaticInit::Double(value) if value.to_bits() == 0 => write!(f, "\t.zero\t8"),
+            StaticInit::Double(value) => write!(f, "\t.quad\t{}", value.to_bits()),
+            StaticInit::Zero(bytes) => write!(f, "\t.zero\t{}", bytes),
+            StaticInit::String {
+                bytes,
+                null_terminated: true,
+            } => write!(f, "\t.asciz\t\"{}\"", Escaped(bytes)),
+            StaticInit::String {
+                bytes,
+                null_terminated: false,
+            } => write!(f, "\t.ascii\t\"{}\"", Escaped(bytes)),
+            StaticInit::Pointer(name) => write!(f, "\t.quad\t{}", name),
+        }
+    }
+}
+
+/// The contents of a string as the assembler reads them between double
+/// quotes. Bytes other than printable ASCII are written in octal.
+struct Escaped<'a>(&'a [u8]);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &byte in self.0 {
+            match byte {
+                b'"' | b'\\' => write!(f, "\\{}", byte as char)?,
+                b' '..=b'~' => write!(f, "{}", byte as char)?,
+                _ => write!(f, "\\{:03o}", byte)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Mov(AssemblyType::Double, src, dst) => {
+                write!(f, "\tmovsd\t{}, {}", src, dst)
+            }
+            Instruction::Mov(ty, src, dst) => {
+                write!(f, "\tmov{}\t{}, {}", ty, Sized(*ty, src), Sized(*ty, dst))
+            }
+            Instruction::Movsx(src_ty, dst_ty, src, dst) => write!(
+                f,
+                "\tmovs{}{}\t{}, {}",
+                src_ty,
+                dst_ty,
+                Sized(*src_ty, src),
+                Sized(*dst_ty, dst)
+            ),
+            Instruction::MovZeroExtend(src_ty, dst_ty, src, dst) => write!(
+                f,
+                "\tmovz{}{}\t{}, {}",
+                src_ty,
+                dst_ty,
+                Sized(*src_ty, src),
+                Sized(*dst_ty, dst)
+            ),
+            Instruction::Unary(op, ty, operand) => {
+                write!(f, "\t{}{}\t{}", op, ty, Sized(*ty, operand))
+            }
+            Instruction::Binary(
+                op @ (BinaryOperator::Sal | BinaryOperator::Sar | BinaryOperator::Shr),
+                ty,
+                src,
+                dst,
+            ) => {
+                write!(
+                    f,
+                    "\t{}{}\t{}, {}",
+                    op,
+                    ty,
+                    ByteOperand(src),
+                    Sized(*ty, dst)
+                )
+            }
+            // The double-precision forms of these do not follow the
+            // integer mnemonics
+            Instruction::Binary(BinaryOperator::Mult, AssemblyType::Double, src, dst) => {
+                write!(f, "\tmulsd\t{}, {}", src, dst)
+            }
+            Instruction::Binary(BinaryOperator::Xor, AssemblyType::Double, src, dst) => {
+                write!(f, "\txorpd\t{}, {}", src, dst)
+            }
+            Instruction::Binary(op, ty, src, dst) => write!(
+                f,
+                "\t{}{}\t{}, {}",
+                op,
+                ty,
+                Sized(*ty, src),
+                Sized(*ty, dst)
+            ),
+            Instruction::Cmp(AssemblyType::Double, a, b) => write!(f, "\tcomisd\t{}, {}", a, b),
+            Instruction::Cmp(ty, a, b) => {
+                write!(f, "\tcmp{}\t{}, {}", ty, Sized(*ty, a), Sized(*ty, b))
+            }
+            Instruction::Idiv(ty, operand) => write!(f, "\tidiv{}\t{}", ty, Sized(*ty, operand)),
+            Instruction::Div(ty, operand) => write!(f, "\tdiv{}\t{}", ty, Sized(*ty, operand)),
+            Instruction::Cdq(AssemblyType::Quadword) => write!(f, "\tcqo"),
+            Instruction::Cdq(_) => write!(f, "\tcdq"),
+            Instruction::Cvttsd2si(ty, src, dst) => {
+                write!(f, "\tcvttsd2si{}\t{}, {}", ty, src, Sized(*ty, dst))
+            }
+            Instruction::Cvtsi2sd(ty, src, dst) => {
+                write!(f, "\tcvtsi2sd{}\t{}, {}", ty, Sized(*ty, src), dst)
+            }
+            Instruction::Lea(src, dst) => write!(f, "\tleaq\t{}, {}", src, QuadOperand(dst)),
+            Instruction::Jmp(label) => write!(f, "\tjmp\t.L{}", label),
+            Instruction::JmpCC(cc, label) => write!(f, "\tj{}\t.L{}", cc, label),
+            Instruction::SetCC(cc, operand) => write!(f, "\tset{}\t{}", cc, ByteOperand(operand)),
+            Instruction::Label(label) => write!(f, ".L{}:", label),
+            Instruction::AllocateStack(size) => write!(f, "\tsubq\t${}, %rsp", size),
+            Instruction::DeallocateStack(size) => write!(f, "\taddq\t${}, %rsp", size),
+            Instruction::Push(operand) => write!(f, "\tpushq\t{}", QuadOperand(operand)),
+            Instruction::Call { name, plt: true } => write!(f, "\tcall\t{}@PLT", name),
+            Instruction::Call { name, plt: false } => write!(f, "\tcall\t{}", name),
+            Instruction::Ret => {
+                writeln!(f, "\tmovq\t%rbp, %rsp")?;
+                writeln!(f, "\tpopq\t%rbp")?;
+                write!(f, "\tret")
+            }
+        }
+    }
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnaryOperator::Neg => write!(f, "neg"),
+            UnaryOperator::Not => write!(f, "not"),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryOperator::Add => write!(f, "add"),
+            BinaryOperator::Sub => write!(f, "sub"),
+            BinaryOperator::Mult => write!(f, "imul"),
+            BinaryOperator::And => write!(f, "and"),
+            BinaryOperator::Or => write!(f, "or"),
+            BinaryOperator::Xor => write!(f, "xor"),
+            BinaryOperator::Sal => write!(f, "sal"),
+            BinaryOperator::Sar => write!(f, "sar"),
+            BinaryOperator::Shr => write!(f, "shr"),
+            BinaryOperator::DivDouble => write!(f, "div"),
+        }
+    }
+}
+
+/// The instruction suffix for the operand size
+impl fmt::Display for AssemblyType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssemblyType::Byte => write!(f, "b"),
+            AssemblyType::Longword => write!(f, "l"),
+            AssemblyType::Quadword => write!(f, "q"),
+            AssemblyType::Double => write!(f, "sd"),
+        }
+    }
+}
+
+impl fmt::Display for CondCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CondCode::E => write!(f, "e"),
+            CondCode::NE => write!(f, "ne"),
+            CondCode::L => write!(f, "l"),
+            CondCode::LE => write!(f, "le"),
+            CondCode::G => write!(f, "g"),
+            CondCode::GE => write!(f, "ge"),
+            CondCode::A => write!(f, "a"),
+            CondCode::AE => write!(f, "ae"),
+            CondCode::B => write!(f, "b"),
+            CondCode::BE => write!(f, "be"),
+            CondCode::P => write!(f, "p"),
+            CondCode::NP => write!(f, "np"),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Imm(value) => write!(f, "${}", value),
+            Operand::Reg(reg) => write!(f, "{}", reg),
+            Operand::Stack(offset) => write!(f, "{}(%rbp)", offset),
+            Operand::Memory(reg, offset) => {
+                write!(f, "{}({})", offset, QuadOperand(&Operand::Reg(*reg)))
+            }
+            Operand::Indexed(base, index, scale) => write!(
+                f,
+                "({}, {}, {})",
+                QuadOperand(&Operand::Reg(*base)),
+                QuadOperand(&Operand::Reg(*index)),
+                scale
+            ),
+            Operand::Data(name) => write!(f, "{}(%rip)", name),
+            Operand::Pseudo(name) | Operand::PseudoMem(name, _) => {
+                unreachable!("pseudo register {} was not replaced", name)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Reg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reg::AX => write!(f, "%eax"),
+            Reg::CX => write!(f, "%ecx"),
+            Reg::DX => write!(f, "%edx"),
+            Reg::DI => write!(f, "%edi"),
+            Reg::SI => write!(f, "%esi"),
+            Reg::R8 => write!(f, "%r8d"),
+            Reg::R9 => write!(f, "%r9d"),
+            Reg::R10 => write!(f, "%r10d"),
+            Reg::R11 => write!(f, "%r11d"),
+            Reg::XMM0 => write!(f, "%xmm0"),
+            Reg::XMM1 => write!(f, "%xmm1"),
+            Reg::XMM2 => write!(f, "%xmm2"),
+            Reg::XMM3 => write!(f, "%xmm3"),
+            Reg::XMM4 => write!(f, "%xmm4"),
+            Reg::XMM5 => write!(f, "%xmm5"),
+            Reg::XMM6 => write!(f, "%xmm6"),
+            Reg::XMM7 => write!(f, "%xmm7"),
+            Reg::XMM14 => write!(f, "%xmm14"),
+            Reg::XMM15 => write!(f, "%xmm15"),
+        }
+    }
+}
+
+/// An operand as used by instructions with the given operand size
+struct Sized<'a>(AssemblyType, &'a Operand);
+
+impl fmt::Display for Sized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            AssemblyType::Longword | AssemblyType::Double => write!(f, "{}", self.1),
+            AssemblyType::Quadword => write!(f, "{}", QuadOperand(self.1)),
+            AssemblyType::Byte => write!(f, "{}", ByteOperand(self.1)),
+        }
+    }
+}
+
+/// An operand as used by instructions that access a single byte
+struct ByteOperand<'a>(&'a Operand);
+
+impl fmt::Display for ByteOperand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Operand::Reg(Reg::AX) => write!(f, "%al"),
+            Operand::Reg(Reg::CX) => write!(f, "%cl"),
+            Operand::Reg(Reg::DX) => write!(f, "%dl"),
+            Operand::Reg(Reg::DI) => write!(f, "%dil"),
+            Operand::Reg(Reg::SI) => write!(f, "%sil"),
+            Operand::Reg(Reg::R8) => write!(f, "%r8b"),
+            Operand::Reg(Reg::R9) => write!(f, "%r9b"),
+            Operand::Reg(Reg::R10) => write!(f, "%r10b"),
+            Operand::Reg(Reg::R11) => write!(f, "%r11b"),
+            operand => write!(f, "{}", operand),
+        }
+    }
+}
+
+/// An operand as used by instructions that access eight bytes
+struct QuadOperand<'a>(&'a Operand);
+
+impl fmt::Display for QuadOperand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Operand::Reg(Reg::AX) => write!(f, "%rax"),
+            Operand::Reg(Reg::CX) => write!(f, "%rcx"),
+            Operand::Reg(Reg::DX) => write!(f, "%rdx"),
+            Operand::Reg(Reg::DI) => write!(f, "%rdi"),
+            Operand::Reg(Reg::SI) => write!(f, "%rsi"),
+            Operand::Reg(Reg::R8) => write!(f, "%r8"),
+            Operand::Reg(Reg::R9) => write!(f, "%r9"),
+            Operand::Reg(Reg::R10) => write!(f, "%r10"),
+            Operand::Reg(Reg::R11) => write!(f, "%r11"),
+            operand => write!(f, "{}", operand),
+        }
+    }
+}
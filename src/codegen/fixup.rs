@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use crate::codegen::asm::*;
+use crate::codegen::{alignment, symbol_type};
+use crate::semantic::symbols::SymbolTable;
+
+/// Replace every pseudo register with a stack slot relative to RBP, or
+/// with a data operand for variables with static storage duration, and
+/// reserve the stack space needed at the start of the function. Each
+/// slot is aligned as its type requires.
+pub fn replace_pseudo_registers(function: &mut Function, symbols: &SymbolTable) {
+    let mut offsets: HashMap<String, i32> = HashMap::new();
+    let mut stack_size = 0;
+    let mut replace = |operand: &mut Operand| {
+        let (name, offset) = match operand {
+            Operand::Pseudo(name) if symbols.is_static(name) => {
+                *operand = Operand::Data(name.clone());
+                return;
+            }
+            Operand::Pseudo(name) => (name, 0),
+            // Only local arrays are initialized element by element
+            Operand::PseudoMem(name, offset) => (name, *offset),
+            _ => return,
+        };
+        let base = *offsets.entry(name.clone()).or_insert_with(|| {
+            let ty = symbol_type(name, symbols);
+            let alignment = alignment(ty);
+            stack_size = (stack_size + ty.size() as i32 + alignment - 1) / alignment * alignment;
+            -stack_size
+        });
+        *operand = Operand::Stack(base + offset);
+    };
+
+    for instruction in &mut function.instructions {
+        match instruction {
+            Instruction::Mov(_, src, dst)
+            | Instruction::Movsx(_, _, src, dst)
+            | Instruction::MovZeroExtend(_, _, src, dst)
+            | Instruction::Binary(_, _, src, dst)
+            | Instruction::Cmp(_, src, dst)
+            | Instruction::Cvttsd2si(_, src, dst)
+            | Instruction::Cvtsi2sd(_, src, dst)
+            | Instruction::Lea(src, dst) => {
+                replace(src);
+                replace(dst);
+            }
+            Instruction::Unary(_, _, operand)
+            | Instruction::Idiv(_, operand)
+            | Instruction::Div(_, operand)
+            | Instruction::SetCC(_, operand)
+            | Instruction::Push(operand) => replace(operand),
+            Instruction::Jmp(_)
+            | Instruction::JmpCC(..)
+            | Instruction::Label(_)
+            | Instruction::AllocateStack(_)
+            | Instruction::DeallocateStack(_)
+            | Instruction::Call { .. }
+            | Instruction::Cdq(_)
+            | Instruction::Ret => {}
+        }
+    }
+
+    // Keep the stack 16-byte aligned
+    let stack_size = (stack_size + 15) / 16 * 16;
+    if stack_size > 0 {
+        function
+            .instructions
+            .insert(0, Instruction::AllocateStack(stack_size));
+    }
+}
+
+/// Rewrite instructions whose operands are not valid in x86-64, such
+/// as moves between two memory locations, using scratch registers.
+/// Doubles use XMM14 and XMM15, which are never used for arguments.
+pub fn fixup_instructions(function: &mut Function) {
+    let r10 = Operand::Reg(Reg::R10);
+    let r11 = Operand::Reg(Reg::R11);
+    let xmm14 = Operand::Reg(Reg::XMM14);
+    let xmm15 = Operand::Reg(Reg::XMM15);
+    let mut instructions = Vec::with_capacity(function.instructions.len());
+    for instruction in function.instructions.drain(..) {
+        match instruction {
+            Instruction::Mov(AssemblyType::Double, src, dst)
+                if src.is_memory() && dst.is_memory() =>
+            {
+                instructions.push(Instruction::Mov(AssemblyType::Double, src, xmm14.clone()));
+                instructions.push(Instruction::Mov(AssemblyType::Double, xmm14.clone(), dst));
+            }
+            // The floating-point arithmetic instructions and comisd only
+            // take a register as their destination or second operand
+            Instruction::Binary(op, AssemblyType::Double, src, dst) if dst.is_memory() => {
+                let ty = AssemblyType::Double;
+                instructions.push(Instruction::Mov(ty, dst.clone(), xmm15.clone()));
+                instructions.push(Instruction::Binary(op, ty, src, xmm15.clone()));
+                instructions.push(Instruction::Mov(ty, xmm15.clone(), dst));
+            }
+            Instruction::Cmp(AssemblyType::Double, a, b) if b.is_memory() => {
+                let ty = AssemblyType::Double;
+                instructions.push(Instruction::Mov(ty, b, xmm15.clone()));
+                instructions.push(Instruction::Cmp(ty, a, xmm15.clone()));
+            }
+            // lea must write to a register
+            Instruction::Lea(src, dst) if dst.is_memory() => {
+                instructions.push(Instruction::Lea(src, r11.clone()));
+                instructions.push(Instruction::Mov(AssemblyType::Quadword, r11.clone(), dst));
+            }
+            // cvttsd2si must write to a general-purpose register
+            Instruction::Cvttsd2si(ty, src, dst) if dst.is_memory() => {
+                instructions.push(Instruction::Cvttsd2si(ty, src, r11.clone()));
+                instructions.push(Instruction::Mov(ty, r11.clone(), dst));
+            }
+            // cvtsi2sd cannot take a constant and must write to an XMM
+            // register
+            Instruction::Cvtsi2sd(ty, src, dst) => {
+                let src = match src {
+                    src @ Operand::Imm(_) => {
+                        instructions.push(Instruction::Mov(ty, src, r10.clone()));
+                        r10.clone()
+                    }
+                    src => src,
+                };
+                if dst.is_memory() {
+                    instructions.push(Instruction::Cvtsi2sd(ty, src, xmm15.clone()));
+                    instructions.push(Instruction::Mov(AssemblyType::Double, xmm15.clone(), dst));
+                } else {
+                    instructions.push(Instruction::Cvtsi2sd(ty, src, dst));
+                }
+            }
+            // Only movq to a register can take a constant that does not
+            // fit in 32 bits
+            Instruction::Mov(ty, src, dst)
+                if (is_large_immediate(&src) && dst.is_memory())
+                    || (src.is_memory() && dst.is_memory()) =>
+            {
+                instructions.push(Instruction::Mov(ty, src, r10.clone()));
+                instructions.push(Instruction::Mov(ty, r10.clone(), dst));
+            }
+            Instruction::Movsx(src_ty, dst_ty, src, dst) => fix_extension(
+                Instruction::Movsx,
+                src_ty,
+                dst_ty,
+                src,
+                dst,
+                &mut instructions,
+            ),
+            Instruction::MovZeroExtend(src_ty, dst_ty, src, dst) => fix_extension(
+                Instruction::MovZeroExtend,
+                src_ty,
+                dst_ty,
+                src,
+                dst,
+                &mut instructions,
+            ),
+            Instruction::Binary(op, ty, src, dst) => {
+                let src = if is_large_immediate(&src) || (src.is_memory() && dst.is_memory()) {
+                    instructions.push(Instruction::Mov(ty, src, r10.clone()));
+                    r10.clone()
+                } else {
+                    src
+                };
+                // imul cannot write its result to memory
+                if op == BinaryOperator::Mult && dst.is_memory() {
+                    instructions.push(Instruction::Mov(ty, dst.clone(), r11.clone()));
+                    instructions.push(Instruction::Binary(op, ty, src, r11.clone()));
+                    instructions.push(Instruction::Mov(ty, r11.clone(), dst));
+                } else {
+                    instructions.push(Instruction::Binary(op, ty, src, dst));
+                }
+            }
+            // idiv and div cannot take a constant operand
+            Instruction::Idiv(ty, operand @ Operand::Imm(_)) => {
+                instructions.push(Instruction::Mov(ty, operand, r10.clone()));
+                instructions.push(Instruction::Idiv(ty, r10.clone()));
+            }
+            Instruction::Div(ty, operand @ Operand::Imm(_)) => {
+                instructions.push(Instruction::Mov(ty, operand, r10.clone()));
+                instructions.push(Instruction::Div(ty, r10.clone()));
+            }
+            Instruction::Cmp(ty, a, b) => {
+                let a = if is_large_immediate(&a) || (a.is_memory() && b.is_memory()) {
+                    instructions.push(Instruction::Mov(ty, a, r10.clone()));
+                    r10.clone()
+                } else {
+                    a
+                };
+                // The second operand of cmp cannot be a constant
+                if let Operand::Imm(_) = b {
+                    instructions.push(Instruction::Mov(ty, b, r11.clone()));
+                    instructions.push(Instruction::Cmp(ty, a, r11.clone()));
+                } else {
+                    instructions.push(Instruction::Cmp(ty, a, b));
+                }
+            }
+            Instruction::Push(operand) if is_large_immediate(&operand) => {
+                instructions.push(Instruction::Mov(
+                    AssemblyType::Quadword,
+                    operand,
+                    r10.clone(),
+                ));
+                instructions.push(Instruction::Push(r10.clone()));
+            }
+            instruction => instructions.push(instruction),
+        }
+    }
+    function.instructions = instructions;
+}
+
+/// movsx and movzx cannot take a constant source or a memory
+/// destination, so those go through R10 and R11
+fn fix_extension(
+    extend: fn(AssemblyType, AssemblyType, Operand, Operand) -> Instruction,
+    src_ty: AssemblyType,
+    dst_ty: AssemblyType,
+    src: Operand,
+    dst: Operand,
+    instructions: &mut Vec<Instruction>,
+) {
+    let src = match src {
+        src @ Operand::Imm(_) => {
+            instructions.push(Instruction::Mov(src_ty, src, Operand::Reg(Reg::R10)));
+            Operand::Reg(Reg::R10)
+        }
+        src => src,
+    };
+    if dst.is_memory() {
+        instructions.push(extend(src_ty, dst_ty, src, Operand::Reg(Reg::R11)));
+        instructions.push(Instruction::Mov(dst_ty, Operand::Reg(Reg::R11), dst));
+    } else {
+        instructions.push(extend(src_ty, dst_ty, src, dst));
+    }
+}
+
+/// Whether an operand is a constant that cannot be encoded as a 32-bit
+/// immediate
+fn is_large_immediate(operand: &Operand) -> bool {
+    matches!(operand, Operand::Imm(value) if i32::try_from(*value).is_err())
+}
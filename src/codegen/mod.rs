@@ -0,0 +1,787 @@
+pub mod asm;
+pub mod emit;
+mod fixup;
+#[cfg(test)]
+mod tests;
+
+use crate::codegen::asm::*;
+use crate::ir::tacky;
+use crate::parser::ast::{Const, Type};
+use crate::semantic::symbols::{StaticInit, SymbolTable};
+
+/// Registers used for the first integer arguments of a call, in order,
+/// as specified by the System V AMD64 ABI
+const ARG_REGISTERS: [Reg; 6] = [Reg::DI, Reg::SI, Reg::DX, Reg::CX, Reg::R8, Reg::R9];
+
+/// Registers used for the first floating-point arguments of a call
+const DOUBLE_ARG_REGISTERS: [Reg; 8] = [
+    Reg::XMM0,
+    Reg::XMM1,
+    Reg::XMM2,
+    Reg::XMM3,
+    Reg::XMM4,
+    Reg::XMM5,
+    Reg::XMM6,
+    Reg::XMM7,
+];
+
+/// Lower the IR to the assembly AST, assign stack slots to the
+/// pseudo registers and rewrite instructions that x86-64 cannot encode
+pub fn generate(program: &tacky::Program, symbols: &SymbolTable) -> Program {
+    let mut constants = Constants::default();
+    let mut top_level: Vec<TopLevel> = program
+        .top_level
+        .iter()
+        .map(|item| match item {
+            tacky::TopLevel::Function(f) => {
+                let mut function = function(f, symbols, &mut constants);
+                fixup::replace_pseudo_registers(&mut function, symbols);
+                fixup::fixup_instructions(&mut function);
+                TopLevel::Function(function)
+            }
+            tacky::TopLevel::StaticVariable(var) => TopLevel::StaticVariable(StaticVariable {
+                name: var.name.clone(),
+                global: var.global,
+                alignment: alignment(symbol_type(&var.name, symbols)),
+                init: var.init.clone(),
+            }),
+            tacky::TopLevel::StaticConstant(constant) => TopLevel::StaticConstant(StaticConstant {
+                name: constant.name.clone(),
+                alignment: alignment(symbol_type(&constant.name, symbols)),
+                init: constant.init.clone(),
+            }),
+        })
+        .collect();
+    top_level.extend(constants.0.into_iter().map(TopLevel::StaticConstant));
+    Program { top_level }
+}
+
+/// Floating-point constants used by the generated code. There are no
+/// floating-point immediates, so each distinct value is stored once in
+/// read-only data and read from memory.
+#[derive(Default)]
+struct Constants(Vec<StaticConstant>);
+
+impl Constants {
+    fn operand(&mut self, value: f64, alignment: i32) -> Operand {
+        // Compare the bits, so that 0.0 and -0.0 are kept apart
+        let existing = self.0.iter().find(|constant| {
+            constant.alignment == alignment
+                && matches!(constant.init, StaticInit::Double(v) if v.to_bits() == value.to_bits())
+        });
+        let name = match existing {
+            Some(constant) => constant.name.clone(),
+            None => {
+                let name = format!(".Ldouble.{}", self.0.len());
+                self.0.push(StaticConstant {
+                    name: name.clone(),
+                    alignment,
+                    init: StaticInit::Double(value),
+                });
+                name
+            }
+        };
+        Operand::Data(name)
+    }
+}
+
+fn function(
+    function: &tacky::Function,
+    symbols: &SymbolTable,
+    constants: &mut Constants,
+) -> Function {
+    let mut instructions = Vec::new();
+    // Copy the parameters out of the argument registers and the caller's
+    // frame, where they sit above the return address and saved RBP
+    let (int_params, double_params, stack_params) =
+        classify(&function.params, |param| var_type(param, symbols));
+    for (param, reg) in int_params.iter().zip(ARG_REGISTERS) {
+        let ty = var_type(param, symbols);
+        instructions.push(Instruction::Mov(
+            ty,
+            Operand::Reg(reg),
+            Operand::Pseudo(param.to_string()),
+        ));
+    }
+    for (param, reg) in double_params.iter().zip(DOUBLE_ARG_REGISTERS) {
+        instructions.push(Instruction::Mov(
+            AssemblyType::Double,
+            Operand::Reg(reg),
+            Operand::Pseudo(param.to_string()),
+        ));
+    }
+    for (i, param) in stack_params.iter().enumerate() {
+        let ty = var_type(param, symbols);
+        instructions.push(Instruction::Mov(
+            ty,
+            Operand::Stack(16 + 8 * i as i32),
+            Operand::Pseudo(param.to_string()),
+        ));
+    }
+    for instruction in &function.body {
+        lower(instruction, symbols, constants, &mut instructions);
+    }
+    Function {
+        name: function.name.clone(),
+        global: function.global,
+        instructions,
+    }
+}
+
+/// Split values into those passed in general-purpose registers, those
+/// passed in XMM registers and those passed on the stack, in the order
+/// the System V ABI assigns them
+fn classify<T>(values: &[T], ty: impl Fn(&T) -> AssemblyType) -> (Vec<&T>, Vec<&T>, Vec<&T>) {
+    let mut ints = Vec::new();
+    let mut doubles = Vec::new();
+    let mut stack = Vec::new();
+    for value in values {
+        if ty(value) == AssemblyType::Double {
+            if doubles.len() < DOUBLE_ARG_REGISTERS.len() {
+                doubles.push(value);
+            } else {
+                stack.push(value);
+            }
+        } else if ints.len() < ARG_REGISTERS.len() {
+            ints.push(value);
+        } else {
+            stack.push(value);
+        }
+    }
+    (ints, doubles, stack)
+}
+
+fn lower(
+    instruction: &tacky::Instruction,
+    symbols: &SymbolTable,
+    constants: &mut Constants,
+    instructions: &mut Vec<Instruction>,
+) {
+    let ty = |val: &tacky::Val| val_type(val, symbols);
+    match instruction {
+        tacky::Instruction::Return(val) => {
+            instructions.push(Instruction::Mov(
+                ty(val),
+                operand(val, constants),
+                Operand::Reg(return_register(ty(val))),
+            ));
+            instructions.push(Instruction::Ret);
+        }
+        tacky::Instruction::SignExtend { src, dst } => {
+            instructions.push(Instruction::Movsx(
+                ty(src),
+                ty(dst),
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::ZeroExtend { src, dst } if ty(src) == AssemblyType::Byte => {
+            instructions.push(Instruction::MovZeroExtend(
+                AssemblyType::Byte,
+                ty(dst),
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+        }
+        // A longword move into a register clears the upper half, and
+        // movq cannot take a longword source, so go through R11
+        tacky::Instruction::ZeroExtend { src, dst } => {
+            instructions.push(Instruction::Mov(
+                AssemblyType::Longword,
+                operand(src, constants),
+                Operand::Reg(Reg::R11),
+            ));
+            instructions.push(Instruction::Mov(
+                AssemblyType::Quadword,
+                Operand::Reg(Reg::R11),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Truncate { src, dst } => {
+            instructions.push(Instruction::Mov(
+                ty(dst),
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+        }
+        // The conversions are signed, so an unsigned int goes through a
+        // quadword, where every value it can hold is non-negative. There
+        // are no byte forms, so characters go through a longword.
+        tacky::Instruction::DoubleToInt { src, dst } => match c_type(dst, symbols) {
+            Type::Char | Type::SChar | Type::UChar => {
+                instructions.push(Instruction::Cvttsd2si(
+                    AssemblyType::Longword,
+                    operand(src, constants),
+                    Operand::Reg(Reg::R11),
+                ));
+                instructions.push(Instruction::Mov(
+                    AssemblyType::Byte,
+                    Operand::Reg(Reg::R11),
+                    operand(dst, constants),
+                ));
+            }
+            Type::UInt => {
+                instructions.push(Instruction::Cvttsd2si(
+                    AssemblyType::Quadword,
+                    operand(src, constants),
+                    Operand::Reg(Reg::R11),
+                ));
+                instructions.push(Instruction::Mov(
+                    AssemblyType::Longword,
+                    Operand::Reg(Reg::R11),
+                    operand(dst, constants),
+                ));
+            }
+            _ => instructions.push(Instruction::Cvttsd2si(
+                ty(dst),
+                operand(src, constants),
+                operand(dst, constants),
+            )),
+        },
+        tacky::Instruction::IntToDouble { src, dst } => match c_type(src, symbols) {
+            Type::Char | Type::SChar => {
+                instructions.push(Instruction::Movsx(
+                    AssemblyType::Byte,
+                    AssemblyType::Longword,
+                    operand(src, constants),
+                    Operand::Reg(Reg::R11),
+                ));
+                instructions.push(Instruction::Cvtsi2sd(
+                    AssemblyType::Longword,
+                    Operand::Reg(Reg::R11),
+                    operand(dst, constants),
+                ));
+            }
+            Type::UChar => {
+                instructions.push(Instruction::MovZeroExtend(
+                    AssemblyType::Byte,
+                    AssemblyType::Longword,
+                    operand(src, constants),
+                    Operand::Reg(Reg::R11),
+                ));
+                instructions.push(Instruction::Cvtsi2sd(
+                    AssemblyType::Longword,
+                    Operand::Reg(Reg::R11),
+                    operand(dst, constants),
+                ));
+            }
+            Type::UInt => {
+                instructions.push(Instruction::Mov(
+                    AssemblyType::Longword,
+                    operand(src, constants),
+                    Operand::Reg(Reg::R11),
+                ));
+                instructions.push(Instruction::Cvtsi2sd(
+                    AssemblyType::Quadword,
+                    Operand::Reg(Reg::R11),
+                    operand(dst, constants),
+                ));
+            }
+            _ => instructions.push(Instruction::Cvtsi2sd(
+                ty(src),
+                operand(src, constants),
+                operand(dst, constants),
+            )),
+        },
+        tacky::Instruction::Unary {
+            op: tacky::UnaryOperator::Negate,
+            src,
+            dst,
+        } if ty(src) == AssemblyType::Double => {
+            // Flip the sign bit. xorpd reads sixteen bytes from memory,
+            // which must be aligned to sixteen bytes.
+            let sign_bit = constants.operand(-0.0, 16);
+            instructions.push(Instruction::Mov(
+                AssemblyType::Double,
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::Binary(
+                BinaryOperator::Xor,
+                AssemblyType::Double,
+                sign_bit,
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Unary {
+            op: tacky::UnaryOperator::Not,
+            src,
+            dst,
+        } => {
+            instructions.push(Instruction::Cmp(
+                ty(src),
+                Operand::Imm(0),
+                operand(src, constants),
+            ));
+            instructions.push(Instruction::Mov(
+                ty(dst),
+                Operand::Imm(0),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::SetCC(CondCode::E, operand(dst, constants)));
+        }
+        tacky::Instruction::Unary { op, src, dst } => {
+            let op = match op {
+                tacky::UnaryOperator::Negate => UnaryOperator::Neg,
+                _ => UnaryOperator::Not,
+            };
+            instructions.push(Instruction::Mov(
+                ty(src),
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::Unary(op, ty(dst), operand(dst, constants)));
+        }
+        tacky::Instruction::Binary {
+            op: tacky::BinaryOperator::Divide,
+            src1,
+            src2,
+            dst,
+        } if ty(src1) == AssemblyType::Double => {
+            instructions.push(Instruction::Mov(
+                AssemblyType::Double,
+                operand(src1, constants),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::Binary(
+                BinaryOperator::DivDouble,
+                AssemblyType::Double,
+                operand(src2, constants),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Binary {
+            op: op @ (tacky::BinaryOperator::Divide | tacky::BinaryOperator::Remainder),
+            src1,
+            src2,
+            dst,
+        } => {
+            // idiv and div divide DX:AX, leaving the quotient in AX and
+            // the remainder in DX. The dividend is sign extended into DX
+            // for signed division and zero extended otherwise.
+            let result = if *op == tacky::BinaryOperator::Divide {
+                Reg::AX
+            } else {
+                Reg::DX
+            };
+            let signed = is_signed(src1, symbols);
+            let ty = ty(src1);
+            instructions.push(Instruction::Mov(
+                ty,
+                operand(src1, constants),
+                Operand::Reg(Reg::AX),
+            ));
+            if signed {
+                instructions.push(Instruction::Cdq(ty));
+                instructions.push(Instruction::Idiv(ty, operand(src2, constants)));
+            } else {
+                instructions.push(Instruction::Mov(ty, Operand::Imm(0), Operand::Reg(Reg::DX)));
+                instructions.push(Instruction::Div(ty, operand(src2, constants)));
+            }
+            instructions.push(Instruction::Mov(
+                ty,
+                Operand::Reg(result),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Binary {
+            op:
+                op @ (tacky::BinaryOperator::Equal
+                | tacky::BinaryOperator::NotEqual
+                | tacky::BinaryOperator::LessThan
+                | tacky::BinaryOperator::LessOrEqual
+                | tacky::BinaryOperator::GreaterThan
+                | tacky::BinaryOperator::GreaterOrEqual),
+            src1,
+            src2,
+            dst,
+        } if ty(src1) == AssemblyType::Double => {
+            let src1 = operand(src1, constants);
+            let src2 = operand(src2, constants);
+            double_comparison(*op, src1, src2, operand(dst, constants), instructions);
+        }
+        tacky::Instruction::Binary {
+            op:
+                op @ (tacky::BinaryOperator::Equal
+                | tacky::BinaryOperator::NotEqual
+                | tacky::BinaryOperator::LessThan
+                | tacky::BinaryOperator::LessOrEqual
+                | tacky::BinaryOperator::GreaterThan
+                | tacky::BinaryOperator::GreaterOrEqual),
+            src1,
+            src2,
+            dst,
+        } => {
+            let signed = is_signed(src1, symbols);
+            let cc = match op {
+                tacky::BinaryOperator::Equal => CondCode::E,
+                tacky::BinaryOperator::NotEqual => CondCode::NE,
+                tacky::BinaryOperator::LessThan if signed => CondCode::L,
+                tacky::BinaryOperator::LessOrEqual if signed => CondCode::LE,
+                tacky::BinaryOperator::GreaterThan if signed => CondCode::G,
+                tacky::BinaryOperator::GreaterOrEqual if signed => CondCode::GE,
+                tacky::BinaryOperator::LessThan => CondCode::B,
+                tacky::BinaryOperator::LessOrEqual => CondCode::BE,
+                tacky::BinaryOperator::GreaterThan => CondCode::A,
+                _ => CondCode::AE,
+            };
+            instructions.push(Instruction::Cmp(
+                ty(src1),
+                operand(src2, constants),
+                operand(src1, constants),
+            ));
+            instructions.push(Instruction::Mov(
+                ty(dst),
+                Operand::Imm(0),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::SetCC(cc, operand(dst, constants)));
+        }
+        tacky::Instruction::Binary {
+            op: op @ (tacky::BinaryOperator::ShiftLeft | tacky::BinaryOperator::ShiftRight),
+            src1,
+            src2,
+            dst,
+        } => {
+            // Right shifts of unsigned values fill with zeros
+            let op = if *op == tacky::BinaryOperator::ShiftLeft {
+                BinaryOperator::Sal
+            } else if is_signed(src1, symbols) {
+                BinaryOperator::Sar
+            } else {
+                BinaryOperator::Shr
+            };
+            // A variable shift count must be in CL. The count may have a
+            // different type from the value being shifted.
+            let count = match operand(src2, constants) {
+                count @ Operand::Imm(_) => count,
+                count => {
+                    instructions.push(Instruction::Mov(ty(src2), count, Operand::Reg(Reg::CX)));
+                    Operand::Reg(Reg::CX)
+                }
+            };
+            instructions.push(Instruction::Mov(
+                ty(src1),
+                operand(src1, constants),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::Binary(
+                op,
+                ty(dst),
+                count,
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Binary {
+            op,
+            src1,
+            src2,
+            dst,
+        } => {
+            let op = match op {
+                tacky::BinaryOperator::Add => BinaryOperator::Add,
+                tacky::BinaryOperator::Subtract => BinaryOperator::Sub,
+                tacky::BinaryOperator::Multiply => BinaryOperator::Mult,
+                tacky::BinaryOperator::BitwiseAnd => BinaryOperator::And,
+                tacky::BinaryOperator::BitwiseOr => BinaryOperator::Or,
+                tacky::BinaryOperator::BitwiseXor => BinaryOperator::Xor,
+                _ => unreachable!("division, shifts and comparisons are lowered separately"),
+            };
+            instructions.push(Instruction::Mov(
+                ty(src1),
+                operand(src1, constants),
+                operand(dst, constants),
+            ));
+            instructions.push(Instruction::Binary(
+                op,
+                ty(dst),
+                operand(src2, constants),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Copy { src, dst } => {
+            instructions.push(Instruction::Mov(
+                ty(src),
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::GetAddress { src, dst } => {
+            instructions.push(Instruction::Lea(
+                operand(src, constants),
+                operand(dst, constants),
+            ));
+        }
+        // Pointers are dereferenced through AX, which is free between
+        // instructions
+        tacky::Instruction::Load { src_ptr, dst } => {
+            instructions.push(Instruction::Mov(
+                AssemblyType::Quadword,
+                operand(src_ptr, constants),
+                Operand::Reg(Reg::AX),
+            ));
+            instructions.push(Instruction::Mov(
+                ty(dst),
+                Operand::Memory(Reg::AX, 0),
+                operand(dst, constants),
+            ));
+        }
+        tacky::Instruction::Store { src, dst_ptr } => {
+            instructions.push(Instruction::Mov(
+                AssemblyType::Quadword,
+                operand(dst_ptr, constants),
+                Operand::Reg(Reg::AX),
+            ));
+            instructions.push(Instruction::Mov(
+                ty(src),
+                operand(src, constants),
+                Operand::Memory(Reg::AX, 0),
+            ));
+        }
+        // A constant index folds into the displacement. Otherwise the
+        // index goes in DX, scaled by the addressing mode if the element
+        // size allows it.
+        tacky::Instruction::AddPtr {
+            ptr,
+            index,
+            scale,
+            dst,
+        } => {
+            let scale = *scale as i64;
+            instructions.push(Instruction::Mov(
+                AssemblyType::Quadword,
+                operand(ptr, constants),
+                Operand::Reg(Reg::AX),
+            ));
+            let address = match operand(index, constants) {
+                Operand::Imm(index) => Operand::Memory(Reg::AX, (index * scale) as i32),
+                index => {
+                    instructions.push(Instruction::Mov(
+                        AssemblyType::Quadword,
+                        index,
+                        Operand::Reg(Reg::DX),
+                    ));
+                    if matches!(scale, 1 | 2 | 4 | 8) {
+                        Operand::Indexed(Reg::AX, Reg::DX, scale as i32)
+                    } else {
+                        instructions.push(Instruction::Binary(
+                            BinaryOperator::Mult,
+                            AssemblyType::Quadword,
+                            Operand::Imm(scale),
+                            Operand::Reg(Reg::DX),
+                        ));
+                        Operand::Indexed(Reg::AX, Reg::DX, 1)
+                    }
+                }
+            };
+            instructions.push(Instruction::Lea(address, operand(dst, constants)));
+        }
+        tacky::Instruction::CopyToOffset { src, dst, offset } => {
+            instructions.push(Instruction::Mov(
+                ty(src),
+                operand(src, constants),
+                Operand::PseudoMem(dst.clone(), *offset as i32),
+            ));
+        }
+        tacky::Instruction::Jump(target) => {
+            instructions.push(Instruction::Jmp(target.clone()));
+        }
+        tacky::Instruction::JumpIfZero(val, target) => {
+            instructions.push(Instruction::Cmp(
+                ty(val),
+                Operand::Imm(0),
+                operand(val, constants),
+            ));
+            instructions.push(Instruction::JmpCC(CondCode::E, target.clone()));
+        }
+        tacky::Instruction::JumpIfNotZero(val, target) => {
+            instructions.push(Instruction::Cmp(
+                ty(val),
+                Operand::Imm(0),
+                operand(val, constants),
+            ));
+            instructions.push(Instruction::JmpCC(CondCode::NE, target.clone()));
+        }
+        tacky::Instruction::Label(label) => {
+            instructions.push(Instruction::Label(label.clone()));
+        }
+        tacky::Instruction::FunCall { name, args, dst } => {
+            call(name, args, dst, symbols, constants, instructions)
+        }
+    }
+}
+
+/// Compare two doubles with comisd. An unordered comparison, where
+/// either operand is NaN, sets ZF, PF and CF together, so only the
+/// "above" conditions can be used directly: less than is done as
+/// greater than with the operands swapped, and equality also checks
+/// the parity flag.
+fn double_comparison(
+    op: tacky::BinaryOperator,
+    src1: Operand,
+    src2: Operand,
+    dst: Operand,
+    instructions: &mut Vec<Instruction>,
+) {
+    let (a, b, cc) = match op {
+        tacky::BinaryOperator::LessThan => (src1, src2, CondCode::A),
+        tacky::BinaryOperator::LessOrEqual => (src1, src2, CondCode::AE),
+        tacky::BinaryOperator::GreaterThan => (src2, src1, CondCode::A),
+        tacky::BinaryOperator::GreaterOrEqual => (src2, src1, CondCode::AE),
+        tacky::BinaryOperator::Equal => (src2, src1, CondCode::E),
+        _ => (src2, src1, CondCode::NE),
+    };
+    let dst_ty = AssemblyType::Longword;
+    instructions.push(Instruction::Cmp(AssemblyType::Double, a, b));
+    instructions.push(Instruction::Mov(dst_ty, Operand::Imm(0), dst.clone()));
+    instructions.push(Instruction::SetCC(cc, dst.clone()));
+    let (parity, op) = match op {
+        tacky::BinaryOperator::Equal => (CondCode::NP, BinaryOperator::And),
+        tacky::BinaryOperator::NotEqual => (CondCode::P, BinaryOperator::Or),
+        _ => return,
+    };
+    let r11 = Operand::Reg(Reg::R11);
+    instructions.push(Instruction::Mov(dst_ty, Operand::Imm(0), r11.clone()));
+    instructions.push(Instruction::SetCC(parity, r11.clone()));
+    instructions.push(Instruction::Binary(op, dst_ty, r11, dst));
+}
+
+/// Lower a function call. Arguments that do not fit in registers are
+/// pushed in reverse order, padded so that RSP is 16-byte aligned at the
+/// call instruction.
+fn call(
+    name: &str,
+    args: &[tacky::Val],
+    dst: &tacky::Val,
+    symbols: &SymbolTable,
+    constants: &mut Constants,
+    instructions: &mut Vec<Instruction>,
+) {
+    let (int_args, double_args, stack_args) = classify(args, |arg| val_type(arg, symbols));
+    let padding = if stack_args.len() % 2 == 1 { 8 } else { 0 };
+    if padding != 0 {
+        instructions.push(Instruction::AllocateStack(padding));
+    }
+
+    for (arg, reg) in int_args.iter().zip(ARG_REGISTERS) {
+        instructions.push(Instruction::Mov(
+            val_type(arg, symbols),
+            operand(arg, constants),
+            Operand::Reg(reg),
+        ));
+    }
+    for (arg, reg) in double_args.iter().zip(DOUBLE_ARG_REGISTERS) {
+        instructions.push(Instruction::Mov(
+            AssemblyType::Double,
+            operand(arg, constants),
+            Operand::Reg(reg),
+        ));
+    }
+    for arg in stack_args.iter().rev() {
+        let ty = val_type(arg, symbols);
+        match operand(arg, constants) {
+            arg @ (Operand::Imm(_) | Operand::Reg(_)) => instructions.push(Instruction::Push(arg)),
+            arg if matches!(ty, AssemblyType::Quadword | AssemblyType::Double) => {
+                instructions.push(Instruction::Push(arg))
+            }
+            // pushq reads eight bytes, which could run past the end of a
+            // smaller variable, so go through a register instead
+            arg => {
+                instructions.push(Instruction::Mov(ty, arg, Operand::Reg(Reg::AX)));
+                instructions.push(Instruction::Push(Operand::Reg(Reg::AX)));
+            }
+        }
+    }
+
+    instructions.push(Instruction::Call {
+        name: name.to_string(),
+        plt: !symbols.is_defined_function(name),
+    });
+    let bytes_to_remove = 8 * stack_args.len() as i32 + padding;
+    if bytes_to_remove != 0 {
+        instructions.push(Instruction::DeallocateStack(bytes_to_remove));
+    }
+    let ty = val_type(dst, symbols);
+    instructions.push(Instruction::Mov(
+        ty,
+        Operand::Reg(return_register(ty)),
+        operand(dst, constants),
+    ));
+}
+
+/// The register that holds a return value of the given type
+fn return_register(ty: AssemblyType) -> Reg {
+    match ty {
+        AssemblyType::Double => Reg::XMM0,
+        _ => Reg::AX,
+    }
+}
+
+/// Unsigned constants are stored with the same bits as the signed
+/// immediate the assembler expects. Doubles are read from memory.
+fn operand(val: &tacky::Val, constants: &mut Constants) -> Operand {
+    match val {
+        tacky::Val::Constant(value) => match *value {
+            Const::Char(value) => Operand::Imm(value as i64),
+            Const::UChar(value) => Operand::Imm(value as i8 as i64),
+            Const::Int(value) => Operand::Imm(value as i64),
+            Const::Long(value) => Operand::Imm(value),
+            Const::UInt(value) => Operand::Imm(value as i32 as i64),
+            Const::ULong(value) => Operand::Imm(value as i64),
+            Const::Double(value) => constants.operand(value, 8),
+        },
+        tacky::Val::Var(name) => Operand::Pseudo(name.clone()),
+    }
+}
+
+fn val_type(val: &tacky::Val, symbols: &SymbolTable) -> AssemblyType {
+    match val {
+        tacky::Val::Constant(value) => assembly_type(&value.ty()),
+        tacky::Val::Var(name) => var_type(name, symbols),
+    }
+}
+
+fn var_type(name: &str, symbols: &SymbolTable) -> AssemblyType {
+    assembly_type(symbol_type(name, symbols))
+}
+
+fn is_signed(val: &tacky::Val, symbols: &SymbolTable) -> bool {
+    c_type(val, symbols).is_signed()
+}
+
+/// The C type of a value, for instructions whose lowering depends on
+/// more than the operand size
+fn c_type(val: &tacky::Val, symbols: &SymbolTable) -> Type {
+    match val {
+        tacky::Val::Constant(value) => value.ty(),
+        tacky::Val::Var(name) => symbol_type(name, symbols).clone(),
+    }
+}
+
+/// The alignment of a variable of type `ty`. The System V ABI aligns
+/// arrays of sixteen bytes or more to sixteen, and other arrays like
+/// their elements.
+fn alignment(ty: &Type) -> i32 {
+    match ty {
+        Type::Array(..) if ty.size() >= 16 => 16,
+        Type::Array(element, _) => alignment(element),
+        _ => ty.size() as i32,
+    }
+}
+
+fn symbol_type<'a>(name: &str, symbols: &'a SymbolTable) -> &'a Type {
+    match symbols.get(name) {
+        Some(symbol) => &symbol.ty,
+        None => unreachable!("variable {} is not in the symbol table", name),
+    }
+}
+
+fn assembly_type(ty: &Type) -> AssemblyType {
+    match ty {
+        Type::Double => AssemblyType::Double,
+        Type::Array(..) => unreachable!("arrays are never used as values"),
+        _ if ty.size() == 1 => AssemblyType::Byte,
+        _ if ty.size() == 4 => AssemblyType::Longword,
+        _ => AssemblyType::Quadword,
+    }
+}
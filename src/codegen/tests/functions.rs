@@ -0,0 +1,145 @@
+use super::*;
+
+#[test]
+fn test_parameters_are_copied_from_registers_and_stack() {
+    let input = "int f(int a, int b, int c, int d, int e, int f, int g, int h) { return h; }";
+    let program = generate_from(input);
+    let instructions = &functions(&program)[0].instructions;
+    assert_eq!(instructions[0], Instruction::AllocateStack(32));
+    assert_eq!(
+        instructions[1],
+        Instruction::Mov(
+            AssemblyType::Longword,
+            Operand::Reg(Reg::DI),
+            Operand::Stack(-4)
+        )
+    );
+    assert_eq!(
+        instructions[6],
+        Instruction::Mov(
+            AssemblyType::Longword,
+            Operand::Reg(Reg::R9),
+            Operand::Stack(-24)
+        )
+    );
+    // The seventh and eighth arguments are above the return address
+    assert_eq!(
+        &instructions[7..11],
+        &[
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(16),
+                Operand::Reg(Reg::R10)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Reg(Reg::R10),
+                Operand::Stack(-28)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(24),
+                Operand::Reg(Reg::R10)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Reg(Reg::R10),
+                Operand::Stack(-32)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_call_with_stack_arguments_keeps_alignment() {
+    let input = r#"
+        int f(int a, int b, int c, int d, int e, int f, int g);
+        int main(void) { int x = 7; return f(1, 2, 3, 4, 5, 6, x); }
+    "#;
+    let program = generate_from(input);
+    assert_eq!(functions(&program).len(), 1);
+    let instructions = &functions(&program)[0].instructions;
+    let start = instructions
+        .iter()
+        .position(|i| *i == Instruction::AllocateStack(8))
+        .expect("expected padding before the call");
+    assert_eq!(
+        &instructions[start..start + 12],
+        &[
+            Instruction::AllocateStack(8),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(1),
+                Operand::Reg(Reg::DI)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(2),
+                Operand::Reg(Reg::SI)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(3),
+                Operand::Reg(Reg::DX)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(4),
+                Operand::Reg(Reg::CX)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(5),
+                Operand::Reg(Reg::R8)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(6),
+                Operand::Reg(Reg::R9)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(-4),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Push(Operand::Reg(Reg::AX)),
+            Instruction::Call {
+                name: "f".to_string(),
+                plt: true,
+            },
+            Instruction::DeallocateStack(16),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Reg(Reg::AX),
+                Operand::Stack(-8)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_emit_calls() {
+    let input = r#"
+        int putchar(int c);
+        int one(void) { return 1; }
+        int main(void) { putchar(one()); return 0; }
+    "#;
+    let assembly = generate_from(input).to_string();
+    assert!(assembly.contains("\tcall\tone\n"));
+    assert!(assembly.contains(", %edi\n"));
+    assert!(assembly.contains("\tcall\tputchar@PLT\n"));
+    assert!(assembly.contains("\t.globl\tone\n"));
+    assert!(assembly.contains("\t.globl\tmain\n"));
+}
+
+#[test]
+fn test_emit_push() {
+    let push = Instruction::Push(Operand::Reg(Reg::AX));
+    assert_eq!(push.to_string(), "\tpushq\t%rax");
+    let push = Instruction::Push(Operand::Imm(3));
+    assert_eq!(push.to_string(), "\tpushq\t$3");
+    assert_eq!(
+        Instruction::DeallocateStack(16).to_string(),
+        "\taddq\t$16, %rsp"
+    );
+}
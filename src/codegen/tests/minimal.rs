@@ -0,0 +1,183 @@
+use super::*;
+
+#[test]
+fn test_return_constant() {
+    let program = generate_from("int main(void) { return 2; }");
+    assert_eq!(functions(&program)[0].name, "main");
+    assert_eq!(
+        functions(&program)[0].instructions,
+        vec![
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(2),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Ret,
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(0),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Ret,
+        ]
+    );
+}
+
+#[test]
+fn test_emit_linux_assembly() {
+    let assembly = generate_from("int main(void) { return 2; }").to_string();
+    let expected = "\t.globl\tmain
+\t.text
+main:
+\tpushq\t%rbp
+\tmovq\t%rsp, %rbp
+\tmovl\t$2, %eax
+\tmovq\t%rbp, %rsp
+\tpopq\t%rbp
+\tret
+\tmovl\t$0, %eax
+\tmovq\t%rbp, %rsp
+\tpopq\t%rbp
+\tret
+
+\t.section\t.note.GNU-stack,\"\",@progbits
+";
+    assert_eq!(assembly, expected);
+}
+
+#[test]
+fn test_pseudo_registers_on_stack() {
+    let program = generate_from("int main(void) { return ~-3; }");
+    assert_eq!(
+        functions(&program)[0].instructions,
+        vec![
+            Instruction::AllocateStack(16),
+            Instruction::Mov(AssemblyType::Longword, Operand::Imm(3), Operand::Stack(-4)),
+            Instruction::Unary(
+                UnaryOperator::Neg,
+                AssemblyType::Longword,
+                Operand::Stack(-4)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(-4),
+                Operand::Reg(Reg::R10)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Reg(Reg::R10),
+                Operand::Stack(-8)
+            ),
+            Instruction::Unary(
+                UnaryOperator::Not,
+                AssemblyType::Longword,
+                Operand::Stack(-8)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(-8),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Ret,
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(0),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Ret,
+        ]
+    );
+}
+
+#[test]
+fn test_logical_not() {
+    let assembly = generate_from("int main(void) { return !0; }").to_string();
+    assert!(assembly.contains(
+        "\tmovl\t$0, %r11d\n\tcmpl\t$0, %r11d\n\tmovl\t$0, -4(%rbp)\n\tsete\t-4(%rbp)\n"
+    ));
+}
+
+#[test]
+fn test_division_fixups() {
+    let program = generate_from("int main(void) { return 7 / 2; }");
+    assert_eq!(
+        functions(&program)[0].instructions,
+        vec![
+            Instruction::AllocateStack(16),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(7),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Cdq(AssemblyType::Longword),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(2),
+                Operand::Reg(Reg::R10)
+            ),
+            Instruction::Idiv(AssemblyType::Longword, Operand::Reg(Reg::R10)),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Reg(Reg::AX),
+                Operand::Stack(-4)
+            ),
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(-4),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Ret,
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Imm(0),
+                Operand::Reg(Reg::AX)
+            ),
+            Instruction::Ret,
+        ]
+    );
+}
+
+#[test]
+fn test_multiply_into_memory() {
+    let assembly = generate_from("int main(void) { return 3 * 4; }").to_string();
+    assert!(assembly.contains(
+        "\tmovl\t$3, -4(%rbp)\n\tmovl\t-4(%rbp), %r11d\n\timull\t$4, %r11d\n\tmovl\t%r11d, -4(%rbp)\n"
+    ));
+}
+
+#[test]
+fn test_shift_count_in_cl() {
+    let assembly = generate_from("int main(void) { return 1 << (2 + 1); }").to_string();
+    assert!(assembly.contains("\tmovl\t-4(%rbp), %ecx\n"));
+    assert!(assembly.contains("\tsall\t%cl, -8(%rbp)\n"));
+}
+
+#[test]
+fn test_static_variables_use_data_operands() {
+    let program = generate_from("int x = 5; int main(void) { int y = x; return y; }");
+    assert_eq!(
+        functions(&program)[0].instructions[1],
+        Instruction::Mov(
+            AssemblyType::Longword,
+            Operand::Data("x".to_string()),
+            Operand::Reg(Reg::R10)
+        )
+    );
+}
+
+#[test]
+fn test_emit_static_variables() {
+    let input = r#"
+        static int zero;
+        int answer = 42;
+        static int helper(void) { return zero; }
+        int main(void) { return helper() + answer; }
+    "#;
+    let assembly = generate_from(input).to_string();
+    assert!(assembly.contains("\t.globl\tanswer\n\t.data\n\t.balign\t4\nanswer:\n\t.long\t42\n"));
+    assert!(assembly.contains("\t.bss\n\t.balign\t4\nzero:\n\t.zero\t4\n"));
+    assert!(!assembly.contains(".globl\tzero"));
+    assert!(!assembly.contains(".globl\thelper"));
+    assert!(assembly.contains("\tmovl\tzero(%rip), %eax\n"));
+    assert!(assembly.contains("\tcall\thelper\n"));
+}
@@ -0,0 +1,28 @@
+use super::*;
+use crate::ir;
+use crate::parser::Parser;
+use crate::semantic;
+
+mod functions;
+mod minimal;
+mod types;
+
+#[cfg(test)]
+fn generate_from(input: &str) -> Program {
+    let mut program = Parser::new(input).parse_program().expect("parse failed");
+    let mut symbols = semantic::analyze(&mut program).expect("semantic analysis failed");
+    let ir = ir::generate(&program, &mut symbols);
+    generate(&ir, &symbols)
+}
+
+#[cfg(test)]
+fn functions(program: &Program) -> Vec<&Function> {
+    program
+        .top_level
+        .iter()
+        .filter_map(|item| match item {
+            TopLevel::Function(function) => Some(function),
+            TopLevel::StaticVariable(_) | TopLevel::StaticConstant(_) => None,
+        })
+        .collect()
+}
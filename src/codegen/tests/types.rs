@@ -0,0 +1,329 @@
+use super::*;
+
+#[test]
+fn test_long_variables_use_eight_byte_slots() {
+    let program = generate_from("int main(void) { int a = 1; long b = 2; return a + b; }");
+    let instructions = &functions(&program)[0].instructions;
+    assert_eq!(instructions[0], Instruction::AllocateStack(48));
+    assert_eq!(
+        instructions[1],
+        Instruction::Mov(AssemblyType::Longword, Operand::Imm(1), Operand::Stack(-4))
+    );
+    // The long slot is aligned to eight bytes
+    assert_eq!(
+        instructions[2],
+        Instruction::Mov(AssemblyType::Quadword, Operand::Imm(2), Operand::Stack(-16))
+    );
+}
+
+#[test]
+fn test_sign_extension_fixups() {
+    let program = generate_from("long main(void) { int i = 5; return (long) i; }");
+    assert_eq!(
+        &functions(&program)[0].instructions[2..4],
+        &[
+            Instruction::Movsx(
+                AssemblyType::Longword,
+                AssemblyType::Quadword,
+                Operand::Stack(-4),
+                Operand::Reg(Reg::R11)
+            ),
+            Instruction::Mov(
+                AssemblyType::Quadword,
+                Operand::Reg(Reg::R11),
+                Operand::Stack(-16)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_large_immediates() {
+    let assembly = generate_from(
+        "int main(void) { long l = 4294967296; l = l + 4294967296; return l == 4294967296; }",
+    )
+    .to_string();
+    assert!(assembly.contains("\tmovq\t$4294967296, %r10\n\tmovq\t%r10, -8(%rbp)\n"));
+    assert!(assembly.contains("\tmovq\t$4294967296, %r10\n\taddq\t%r10, -16(%rbp)\n"));
+    assert!(assembly.contains("\tmovq\t$4294967296, %r10\n\tcmpq\t%r10, -8(%rbp)\n"));
+}
+
+#[test]
+fn test_truncate_constant() {
+    let assembly = generate_from("int main(void) { return (int) 4294967298; }").to_string();
+    // The conversion is done at compile time
+    assert!(assembly.contains("\tmovl\t$2, %eax\n"));
+}
+
+#[test]
+fn test_emit_quadword_instructions() {
+    let div = Instruction::Idiv(AssemblyType::Quadword, Operand::Reg(Reg::R10));
+    assert_eq!(div.to_string(), "\tidivq\t%r10");
+    assert_eq!(
+        Instruction::Cdq(AssemblyType::Quadword).to_string(),
+        "\tcqo"
+    );
+    let movsx = Instruction::Movsx(
+        AssemblyType::Longword,
+        AssemblyType::Quadword,
+        Operand::Stack(-4),
+        Operand::Reg(Reg::R11),
+    );
+    assert_eq!(movsx.to_string(), "\tmovslq\t-4(%rbp), %r11");
+    let shift = Instruction::Binary(
+        BinaryOperator::Sal,
+        AssemblyType::Quadword,
+        Operand::Reg(Reg::CX),
+        Operand::Reg(Reg::AX),
+    );
+    assert_eq!(shift.to_string(), "\tsalq\t%cl, %rax");
+}
+
+#[test]
+fn test_emit_long_static_variables() {
+    let assembly =
+        generate_from("long big = 8589934592; static long zero; int main(void) { return 0; }")
+            .to_string();
+    assert!(assembly.contains("\t.globl\tbig\n\t.data\n\t.balign\t8\nbig:\n\t.quad\t8589934592\n"));
+    assert!(assembly.contains("\t.bss\n\t.balign\t8\nzero:\n\t.zero\t8\n"));
+}
+
+#[test]
+fn test_unsigned_instructions() {
+    let assembly = generate_from(
+        "int main(void) { unsigned a = 10; unsigned b = 3; return a / b + (a >> b) + (a < b) + (a >= b); }",
+    )
+    .to_string();
+    assert!(assembly.contains("\tmovl\t$0, %edx\n\tdivl\t"));
+    assert!(!assembly.contains("idivl"));
+    assert!(assembly.contains("\tshrl\t%cl, "));
+    assert!(assembly.contains("\tsetb\t"));
+    assert!(assembly.contains("\tsetae\t"));
+}
+
+#[test]
+fn test_zero_extension() {
+    let program =
+        generate_from("int main(void) { unsigned u = 1; unsigned long l = u; return 0; }");
+    assert_eq!(
+        &functions(&program)[0].instructions[2..4],
+        &[
+            Instruction::Mov(
+                AssemblyType::Longword,
+                Operand::Stack(-4),
+                Operand::Reg(Reg::R11)
+            ),
+            Instruction::Mov(
+                AssemblyType::Quadword,
+                Operand::Reg(Reg::R11),
+                Operand::Stack(-16)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_char_instructions() {
+    let assembly =
+        generate_from("int main(void) { char c = 'A'; unsigned char u = 200; return c + u; }")
+            .to_string();
+    // Characters take one byte each, and are extended to int when used
+    assert!(assembly.contains("\tmovb\t$65, -1(%rbp)\n\tmovb\t$-56, -2(%rbp)\n"));
+    assert!(assembly.contains("\tmovsbl\t-1(%rbp), %r11d\n\tmovl\t%r11d, -8(%rbp)\n"));
+    assert!(assembly.contains("\tmovzbl\t-2(%rbp), %r11d\n\tmovl\t%r11d, -12(%rbp)\n"));
+    // Truncation moves the low byte
+    let assembly = generate_from("char f(char c, long l) { return l; }").to_string();
+    assert!(assembly.contains("\tmovb\t%dil, -1(%rbp)\n"));
+    assert!(assembly.contains("\tmovb\t-16(%rbp), %r10b\n\tmovb\t%r10b, -17(%rbp)\n"));
+    assert!(assembly.contains("\tmovb\t-17(%rbp), %al\n"));
+    let movzx = Instruction::MovZeroExtend(
+        AssemblyType::Byte,
+        AssemblyType::Quadword,
+        Operand::Reg(Reg::AX),
+        Operand::Reg(Reg::R11),
+    );
+    assert_eq!(movzx.to_string(), "\tmovzbq\t%al, %r11");
+}
+
+#[test]
+fn test_emit_char_static_variables() {
+    let assembly = generate_from(
+        "char c = 'a'; unsigned char u = 255; static char zero; int main(void) { return 0; }",
+    )
+    .to_string();
+    assert!(assembly.contains("\t.globl\tc\n\t.data\n\t.balign\t1\nc:\n\t.byte\t97\n"));
+    assert!(assembly.contains("u:\n\t.byte\t255\n"));
+    assert!(assembly.contains("\t.bss\n\t.balign\t1\nzero:\n\t.zero\t1\n"));
+}
+
+#[test]
+fn test_double_constants_are_read_only_data() {
+    let assembly =
+        generate_from("double main(void) { double a = 1.5; double b = 1.5; return -(a + b); }")
+            .to_string();
+    // Equal constants share a label
+    assert!(assembly.contains(
+        "\t.section\t.rodata\n\t.balign\t8\n.Ldouble.0:\n\t.quad\t4609434218613702656\n"
+    ));
+    assert_eq!(assembly.matches("4609434218613702656").count(), 1);
+    // Negation flips the sign bit with a sixteen-byte mask
+    assert!(
+        assembly.contains("\t.balign\t16\n.Ldouble.1:\n\t.quad\t9223372036854775808\n\t.zero\t8\n")
+    );
+    assert!(assembly.contains("\txorpd\t.Ldouble.1(%rip), %xmm15\n"));
+    assert!(assembly.contains("\taddsd\t"));
+    assert!(assembly.contains("%xmm0\n"));
+}
+
+#[test]
+fn test_emit_negative_static_doubles() {
+    let assembly = generate_from(
+        "double d = -2.5; double z = -0.0; double p = 0.0; int main(void) { return 0; }",
+    )
+    .to_string();
+    assert!(assembly.contains("d:\n\t.quad\t13836183955189006336\n"));
+    // Negative zero is not all zero bits, so it cannot go in .bss
+    assert!(assembly.contains("\t.data\n\t.balign\t8\nz:\n\t.quad\t9223372036854775808\n"));
+    assert!(assembly.contains("\t.bss\n\t.balign\t8\np:\n\t.zero\t8\n"));
+}
+
+#[test]
+fn test_double_comparisons_handle_nan() {
+    let assembly = generate_from(
+        "int main(void) { double a = 1.0; double b = 2.0; return (a < b) + (a == b) + (a != b); }",
+    )
+    .to_string();
+    // a < b is tested as b > a, which is false when unordered
+    assert!(assembly.contains("\tcomisd\t-8(%rbp), %xmm15\n\tmovl\t$0, "));
+    assert!(assembly.contains("\tseta\t"));
+    assert!(!assembly.contains("\tsetb\t"));
+    assert!(assembly.contains("\tsetnp\t%r11b\n\tandl\t%r11d, "));
+    assert!(assembly.contains("\tsetp\t%r11b\n\torl\t%r11d, "));
+}
+
+#[test]
+fn test_double_arguments_use_xmm_registers() {
+    let program = generate_from(
+        "double f(int a, double b, long c, double d); double main(void) { return f(1, 2.0, 3, 4.0); }",
+    );
+    let instructions = &functions(&program)[0].instructions;
+    assert!(instructions.contains(&Instruction::Mov(
+        AssemblyType::Longword,
+        Operand::Imm(1),
+        Operand::Reg(Reg::DI)
+    )));
+    assert!(instructions.contains(&Instruction::Mov(
+        AssemblyType::Quadword,
+        Operand::Imm(3),
+        Operand::Reg(Reg::SI)
+    )));
+    assert!(instructions.contains(&Instruction::Mov(
+        AssemblyType::Double,
+        Operand::Data(".Ldouble.0".to_string()),
+        Operand::Reg(Reg::XMM0)
+    )));
+    assert!(instructions.contains(&Instruction::Mov(
+        AssemblyType::Double,
+        Operand::Data(".Ldouble.1".to_string()),
+        Operand::Reg(Reg::XMM1)
+    )));
+}
+
+#[test]
+fn test_emit_double_conversions() {
+    let to_int = Instruction::Cvttsd2si(
+        AssemblyType::Quadword,
+        Operand::Reg(Reg::XMM0),
+        Operand::Reg(Reg::R11),
+    );
+    assert_eq!(to_int.to_string(), "\tcvttsd2siq\t%xmm0, %r11");
+    let to_double = Instruction::Cvtsi2sd(
+        AssemblyType::Longword,
+        Operand::Stack(-4),
+        Operand::Reg(Reg::XMM15),
+    );
+    assert_eq!(to_double.to_string(), "\tcvtsi2sdl\t-4(%rbp), %xmm15");
+    let divide = Instruction::Binary(
+        BinaryOperator::DivDouble,
+        AssemblyType::Double,
+        Operand::Reg(Reg::XMM1),
+        Operand::Reg(Reg::XMM0),
+    );
+    assert_eq!(divide.to_string(), "\tdivsd\t%xmm1, %xmm0");
+}
+
+#[test]
+fn test_pointer_instructions() {
+    let assembly =
+        generate_from("int main(void) { int x = 1; int *p = &x; *p = 5; return *p; }").to_string();
+    // lea cannot write to memory
+    assert!(assembly.contains("\tleaq\t-4(%rbp), %r11\n\tmovq\t%r11, "));
+    assert!(assembly.contains("\tmovq\t-24(%rbp), %rax\n\tmovl\t$5, 0(%rax)\n"));
+    assert!(assembly.contains("\tmovq\t-24(%rbp), %rax\n\tmovl\t0(%rax), %r10d\n"));
+}
+
+#[test]
+fn test_emit_memory_operands() {
+    let load = Instruction::Mov(
+        AssemblyType::Quadword,
+        Operand::Memory(Reg::AX, 8),
+        Operand::Reg(Reg::DX),
+    );
+    assert_eq!(load.to_string(), "\tmovq\t8(%rax), %rdx");
+    let lea = Instruction::Lea(Operand::Data("counter".to_string()), Operand::Reg(Reg::DI));
+    assert_eq!(lea.to_string(), "\tleaq\tcounter(%rip), %rdi");
+}
+
+#[test]
+fn test_array_indexing() {
+    let assembly =
+        generate_from("long f(int *a, long i) { long m[2][3]; m[i][1] = a[i]; return m[1][i]; }")
+            .to_string();
+    // Element sizes of 1, 2, 4 or 8 are scaled by the addressing mode
+    assert!(assembly.contains("\tleaq\t(%rax, %rdx, 4), %r11\n"));
+    assert!(assembly.contains("\tleaq\t(%rax, %rdx, 8), %r11\n"));
+    // Rows of 24 bytes need a multiplication first
+    assert!(assembly.contains("\timulq\t$24, %rdx\n\tleaq\t(%rax, %rdx, 1), %r11\n"));
+    // Constant indices become displacements
+    assert!(assembly.contains("\tleaq\t8(%rax), %r11\n"));
+    assert!(assembly.contains("\tleaq\t24(%rax), %r11\n"));
+}
+
+#[test]
+fn test_array_storage() {
+    let program = generate_from(
+        "int small[3] = {1}; long big[2]; int main(void) { int a[2] = {5, 6}; return a[0]; }",
+    );
+    let assembly = program.to_string();
+    // Arrays of sixteen bytes or more are aligned to sixteen
+    assert!(assembly.contains("\t.data\n\t.balign\t4\nsmall:\n\t.long\t1\n\t.zero\t8\n"));
+    assert!(assembly.contains("\t.bss\n\t.balign\t16\nbig:\n\t.zero\t16\n"));
+    let main = &functions(&program)[0].instructions;
+    assert!(main.contains(&Instruction::Mov(
+        AssemblyType::Longword,
+        Operand::Imm(5),
+        Operand::Stack(-8)
+    )));
+    assert!(main.contains(&Instruction::Mov(
+        AssemblyType::Longword,
+        Operand::Imm(6),
+        Operand::Stack(-4)
+    )));
+    assert!(main.contains(&Instruction::Lea(
+        Operand::Stack(-8),
+        Operand::Reg(Reg::R11)
+    )));
+}
+
+#[test]
+fn test_emit_string_constants() {
+    let assembly = generate_from(
+        "char exact[2] = \"ok\"; char *p = \"a\\\"b\\\\\\n\"; int main(void) { return 0; }",
+    )
+    .to_string();
+    assert!(assembly.contains("exact:\n\t.ascii\t\"ok\"\n"));
+    assert!(assembly.contains("p:\n\t.quad\t.Lstring.0\n"));
+    assert!(assembly.contains(
+        "\t.section\t.rodata\n\t.balign\t1\n.Lstring.0:\n\t.asciz\t\"a\\\"b\\\\\\012\"\n"
+    ));
+}
@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cliargs::Args;
+use crate::codegen;
+use crate::ir;
+use crate::parser::Parser;
+use crate::scanner::token::TokenType;
+use crate::scanner::Scanner;
+use crate::semantic;
+
+/// Errors reported by the compiler driver. Each kind of failure exits
+/// with its own status code so that callers can tell them apart.
+#[derive(Debug)]
+pub enum DriverError {
+    Io(PathBuf, io::Error),
+    Preprocess(String),
+    Compile(Vec<String>),
+    Link(String),
+}
+
+impl DriverError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DriverError::Io(..) => 1,
+            DriverError::Preprocess(_) => 2,
+            DriverError::Compile(_) => 3,
+            DriverError::Link(_) => 4,
+        }
+    }
+}
+
+impl fmt::Display for DriverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DriverError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            DriverError::Preprocess(msg) => write!(f, "preprocessor failed: {}", msg),
+            DriverError::Compile(errors) => {
+                for e in errors {
+                    writeln!(f, "{}", e)?;
+                }
+                write!(f, "{} error(s) found", errors.len())
+            }
+            DriverError::Link(msg) => write!(f, "assembler/linker failed: {}", msg),
+        }
+    }
+}
+
+/// The last stage of the pipeline to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Lex,
+    Parse,
+    Tacky,
+    Codegen,
+    Emit,
+    Assemble,
+    Link,
+}
+
+impl From<&Args> for Stage {
+    fn from(args: &Args) -> Self {
+        if args.lex {
+            Stage::Lex
+        } else if args.parse {
+            Stage::Parse
+        } else if args.tacky {
+            Stage::Tacky
+        } else if args.codegen {
+            Stage::Codegen
+        } else if args.assembly {
+            Stage::Emit
+        } else if args.object {
+            Stage::Assemble
+        } else {
+            Stage::Link
+        }
+    }
+}
+
+/// Run the pipeline on each input file: preprocess, compile, assemble
+/// and link, stopping early if a stage flag was given. Intermediate
+/// files are removed once they are no longer needed, whether or not
+/// the later stages succeed.
+pub fn run(args: &Args) -> Result<(), DriverError> {
+    let mut asm_files = Vec::new();
+    let result = build(args, Stage::from(args), &mut asm_files);
+    for asm_file in &asm_files {
+        remove_file(asm_file);
+    }
+    result
+}
+
+/// Build all inputs up to `stage`. Assembly files that are only
+/// needed by the assembler are recorded in `asm_files` for cleanup.
+fn build(args: &Args, stage: Stage, asm_files: &mut Vec<PathBuf>) -> Result<(), DriverError> {
+    let output = match &args.output {
+        Some(output) => PathBuf::from(output),
+        None => Path::new(&args.input[0]).with_extension(""),
+    };
+    if stage == Stage::Link && args.input.iter().any(|input| Path::new(input) == output) {
+        return Err(DriverError::Link(format!(
+            "output file '{}' would overwrite an input file",
+            output.display()
+        )));
+    }
+
+    let mut linker_inputs = Vec::new();
+    for input in &args.input {
+        let input = Path::new(input);
+        if input.extension() != Some("c".as_ref()) {
+            linker_inputs.push(input.to_path_buf());
+            continue;
+        }
+        let Some(assembly) = compile_file(input, stage)? else {
+            continue;
+        };
+        let asm_file = match &args.output {
+            Some(output) if stage == Stage::Emit => PathBuf::from(output),
+            _ => input.with_extension("s"),
+        };
+        fs::write(&asm_file, assembly).map_err(|e| DriverError::Io(asm_file.clone(), e))?;
+        if stage == Stage::Emit {
+            continue;
+        }
+        asm_files.push(asm_file.clone());
+        if stage == Stage::Assemble {
+            let object = match &args.output {
+                Some(output) => PathBuf::from(output),
+                None => input.with_extension("o"),
+            };
+            assemble(&asm_file, &object)?;
+        } else {
+            linker_inputs.push(asm_file);
+        }
+    }
+
+    if stage == Stage::Link {
+        link(&linker_inputs, &output)?;
+    }
+    Ok(())
+}
+
+/// Preprocess and compile a single C source file. Since `-P` drops the
+/// line markers, errors are reported against the preprocessed file,
+/// which is kept in that case so that the positions can be looked up.
+fn compile_file(input: &Path, stage: Stage) -> Result<Option<String>, DriverError> {
+    let preprocessed = input.with_extension("i");
+    preprocess(input, &preprocessed)?;
+    let result = fs::read_to_string(&preprocessed)
+        .map_err(|e| DriverError::Io(preprocessed.clone(), e))
+        .and_then(|source| compile(&source, &preprocessed.to_string_lossy(), stage));
+    if !matches!(result, Err(DriverError::Compile(_))) {
+        remove_file(&preprocessed);
+    }
+    result
+}
+
+/// Compile preprocessed source code into assembly. Returns `None`
+/// when the pipeline is asked to stop before code emission.
+pub fn compile(source: &str, path: &str, stage: Stage) -> Result<Option<String>, DriverError> {
+    if stage == Stage::Lex {
+        let errors = lex(source, path);
+        if !errors.is_empty() {
+            return Err(DriverError::Compile(errors));
+        }
+        return Ok(None);
+    }
+
+    let mut program = Parser::new(source)
+        .parse_program()
+        .map_err(|e| DriverError::Compile(vec![format!("{}:{}: {}", path, e.line(), e)]))?;
+    if stage == Stage::Parse {
+        return Ok(None);
+    }
+
+    let mut symbols = semantic::analyze(&mut program)
+        .map_err(|e| DriverError::Compile(vec![format!("{}:{}: {}", path, e.line(), e)]))?;
+
+    let ir = ir::generate(&program, &mut symbols);
+    if stage == Stage::Tacky {
+        return Ok(None);
+    }
+
+    let assembly = codegen::generate(&ir, &symbols);
+    if stage == Stage::Codegen {
+        return Ok(None);
+    }
+    Ok(Some(assembly.to_string()))
+}
+
+/// Scan the whole source and collect the lexical errors, if any
+fn lex(source: &str, path: &str) -> Vec<String> {
+    let mut scanner = Scanner::new(source);
+    let mut errors = Vec::new();
+    loop {
+        let tok = scanner.scan_token();
+        match tok.ttype {
+            TokenType::Error => errors.push(format!("{}:{}: {}", path, tok.line, tok.lexeme)),
+            TokenType::Eof => break,
+            _ => {}
+        }
+    }
+    errors
+}
+
+fn preprocess(input: &Path, output: &Path) -> Result<(), DriverError> {
+    let status = Command::new("gcc")
+        .arg("-E")
+        .arg("-P")
+        .arg(input)
+        .arg("-o")
+        .arg(output)
+        .status()
+        .map_err(|e| DriverError::Preprocess(format!("could not run gcc: {}", e)))?;
+    if !status.success() {
+        remove_file(output);
+        return Err(DriverError::Preprocess(format!(
+            "gcc exited with {}",
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// Assemble a single file into a relocatable object file
+fn assemble(asm_file: &Path, output: &Path) -> Result<(), DriverError> {
+    let mut gcc = Command::new("gcc");
+    gcc.arg("-c").arg(asm_file).arg("-o").arg(output);
+    run_gcc(gcc)
+}
+
+/// Assemble and link the inputs into an executable
+fn link(inputs: &[PathBuf], output: &Path) -> Result<(), DriverError> {
+    let mut gcc = Command::new("gcc");
+    gcc.args(inputs).arg("-o").arg(output);
+    run_gcc(gcc)
+}
+
+fn run_gcc(mut gcc: Command) -> Result<(), DriverError> {
+    let status = gcc
+        .status()
+        .map_err(|e| DriverError::Link(format!("could not run gcc: {}", e)))?;
+    if !status.success() {
+        return Err(DriverError::Link(format!("gcc exited with {}", status)));
+    }
+    Ok(())
+}
+
+/// Remove an intermediate file. A file that is already gone is not an
+/// error worth reporting at this point.
+fn remove_file(path: &Path) {
+    let _ = fs::remove_file(path);
+}
@@ -0,0 +1,158 @@
+use super::*;
+
+#[test]
+fn test_if_else() {
+    let source = r#"
+        int main(void) {
+            int a = 2;
+            int b;
+            if (a > 1)
+                b = 10;
+            else
+                b = 20;
+            if (a == 0)
+                b = b + 100;
+            return b;
+        }
+    "#;
+    assert_eq!(run_program(source), 10);
+}
+
+#[test]
+fn test_dangling_else() {
+    let source = r#"
+        int main(void) {
+            int a = 0;
+            if (1)
+                if (0)
+                    a = 1;
+                else
+                    a = 2;
+            return a;
+        }
+    "#;
+    assert_eq!(run_program(source), 2);
+}
+
+#[test]
+fn test_else_if_chain() {
+    let source = r#"
+        int main(void) {
+            int x = 7;
+            if (x < 5)
+                return 1;
+            else if (x < 10)
+                return 2;
+            else
+                return 3;
+        }
+    "#;
+    assert_eq!(run_program(source), 2);
+}
+
+#[test]
+fn test_conditional_operator() {
+    assert_eq!(run_program("int main(void) { return 1 ? 2 : 3; }"), 2);
+    assert_eq!(run_program("int main(void) { return 0 ? 2 : 3; }"), 3);
+    assert_eq!(
+        run_program("int main(void) { int a = 5; return a > 4 ? a < 6 ? 10 : 20 : 30; }"),
+        10
+    );
+}
+
+#[test]
+fn test_conditional_evaluates_one_branch() {
+    let source = r#"
+        int main(void) {
+            int a = 0;
+            int b = 0;
+            1 ? (a = 4) : (b = 5);
+            return a * 10 + b;
+        }
+    "#;
+    assert_eq!(run_program(source), 40);
+}
+
+#[test]
+fn test_while_loop() {
+    let source = r#"
+        int main(void) {
+            int i = 0;
+            int sum = 0;
+            while (i < 10) {
+                sum = sum + i;
+                i = i + 1;
+            }
+            return sum;
+        }
+    "#;
+    assert_eq!(run_program(source), 45);
+}
+
+#[test]
+fn test_do_while_runs_body_once() {
+    let source = r#"
+        int main(void) {
+            int a = 0;
+            do
+                a = a + 7;
+            while (0);
+            return a;
+        }
+    "#;
+    assert_eq!(run_program(source), 7);
+}
+
+#[test]
+fn test_for_loop_with_break_and_continue() {
+    let source = r#"
+        int main(void) {
+            int sum = 0;
+            for (int i = 0; ; i = i + 1) {
+                if (i > 20)
+                    break;
+                if (i % 2)
+                    continue;
+                sum = sum + i;
+            }
+            return sum;
+        }
+    "#;
+    assert_eq!(run_program(source), 110);
+}
+
+#[test]
+fn test_nested_loops() {
+    let source = r#"
+        int main(void) {
+            int count = 0;
+            int i;
+            for (i = 0; i < 5; i = i + 1) {
+                int j = 0;
+                while (1) {
+                    j = j + 1;
+                    if (j > i)
+                        break;
+                    count = count + 1;
+                }
+            }
+            do {
+                count = count + 1;
+                continue;
+            } while (count < 12);
+            return count;
+        }
+    "#;
+    assert_eq!(run_program(source), 12);
+}
+
+#[test]
+fn test_break_outside_loop_is_compile_error() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) {\n    break;\n}\n");
+    let err = run_driver(&[&input, "-S"]).unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert!(err
+        .to_string()
+        .contains("prog.i:2: break statement not within a loop"));
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+#[test]
+fn test_recursive_function() {
+    let source = r#"
+        int fib(int n) {
+            if (n < 2)
+                return n;
+            return fib(n - 1) + fib(n - 2);
+        }
+
+        int main(void) {
+            return fib(10);
+        }
+    "#;
+    assert_eq!(run_program(source), 55);
+}
+
+#[test]
+fn test_forward_declaration() {
+    let source = r#"
+        int is_even(int n);
+
+        int is_odd(int n) {
+            return n == 0 ? 0 : is_even(n - 1);
+        }
+
+        int is_even(int n) {
+            return n == 0 ? 1 : is_odd(n - 1);
+        }
+
+        int main(void) {
+            return is_even(10) * 10 + is_odd(7);
+        }
+    "#;
+    assert_eq!(run_program(source), 11);
+}
+
+#[test]
+fn test_arguments_on_the_stack() {
+    let source = r#"
+        int weigh(int a, int b, int c, int d, int e, int f, int g, int h, int i) {
+            return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g - 8 * h - i;
+        }
+
+        int main(void) {
+            int x = 1;
+            return weigh(x, x + 1, 3, 4, 5, 6, x * 7, 2, 1);
+        }
+    "#;
+    assert_eq!(run_program(source), 1 + 4 + 9 + 16 + 25 + 36 + 49 - 16 - 1);
+}
+
+#[test]
+fn test_call_libc() {
+    let source = r#"
+        int putchar(int c);
+
+        int main(void) {
+            putchar(72);
+            putchar(105);
+            putchar(10);
+            return 0;
+        }
+    "#;
+    assert_eq!(run_program_with_output(source), (0, "Hi\n".to_string()));
+}
+
+#[test]
+fn test_call_gcc_compiled_code() {
+    let dir = ScratchDir::new();
+    // Passing through a double makes misaligned stacks crash in printf
+    let helper = dir.write(
+        "helper.c",
+        r#"
+            #include <stdio.h>
+            int sum7(int a, int b, int c, int d, int e, int f, int g) {
+                char buf[64];
+                snprintf(buf, sizeof buf, "%f", (double)g);
+                return a + b + c + d + e + f + g;
+            }
+        "#,
+    );
+    let object = dir.0.join("helper.o");
+    let status = Command::new("gcc")
+        .arg("-c")
+        .arg(&helper)
+        .arg("-o")
+        .arg(&object)
+        .status()
+        .expect("could not run gcc");
+    assert!(status.success());
+    let input = dir.write(
+        "prog.c",
+        r#"
+            int sum7(int a, int b, int c, int d, int e, int f, int g);
+            int main(void) {
+                int total = 0;
+                for (int i = 0; i < 3; i = i + 1)
+                    total = total + sum7(i, 1, 2, 3, 4, 5, 6);
+                return total;
+            }
+        "#,
+    );
+    let output = dir.0.join("prog");
+    run_driver(&[
+        &input,
+        object.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ])
+    .unwrap();
+    let status = Command::new(&output).status().unwrap();
+    assert_eq!(status.code(), Some(3 * 21 + 3));
+}
+
+#[test]
+fn test_call_to_undeclared_function_is_compile_error() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) {\n    return foo();\n}\n");
+    let err = run_driver(&[&input, "-S"]).unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert!(err
+        .to_string()
+        .contains("prog.i:2: call to undeclared function 'foo'"));
+}
+
+#[test]
+fn test_wrong_argument_count_is_compile_error() {
+    let dir = ScratchDir::new();
+    let input = dir.write(
+        "prog.c",
+        "int f(int a) {\n    return a;\n}\nint main(void) {\n    return f(1, 2);\n}\n",
+    );
+    let err = run_driver(&[&input, "-S"]).unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert!(err
+        .to_string()
+        .contains("prog.i:5: function 'f' takes 1 argument(s) but 2 were given"));
+}
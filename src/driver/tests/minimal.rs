@@ -0,0 +1,133 @@
+use super::*;
+
+#[test]
+fn test_return_2() {
+    let source = fs::read_to_string("docs/return_2.c").unwrap();
+    assert_eq!(run_program(&source), 2);
+}
+
+#[test]
+fn test_intermediate_files_removed() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) { return 0; }");
+    run_driver(&[&input]).unwrap();
+    assert!(dir.0.join("prog").exists());
+    assert!(!dir.0.join("prog.i").exists());
+    assert!(!dir.0.join("prog.s").exists());
+}
+
+#[test]
+fn test_stage_flags_produce_no_output() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) { return 0; }");
+    for flag in ["--lex", "--parse", "--tacky", "--codegen"] {
+        run_driver(&[flag, &input]).unwrap();
+    }
+    let mut files: Vec<_> = fs::read_dir(&dir.0)
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["prog.c"]);
+}
+
+#[test]
+fn test_emit_assembly_only() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) { return 0; }");
+    run_driver(&["-S", &input]).unwrap();
+    assert!(dir.0.join("prog.s").exists());
+    assert!(!dir.0.join("prog").exists());
+}
+
+#[test]
+fn test_compile_error_exit_code() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) { return }");
+    let err = run_driver(&[&input]).unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert!(!dir.0.join("prog.s").exists());
+}
+
+#[test]
+fn test_preprocess_error_exit_code() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "#include \"missing.h\"\n");
+    let err = run_driver(&[&input]).unwrap_err();
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn test_link_error_exit_code() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int helper(void) { return 0; }");
+    let err = run_driver(&[&input]).unwrap_err();
+    assert_eq!(err.exit_code(), 4);
+}
+
+#[test]
+fn test_object_file_links_with_gcc() {
+    let dir = ScratchDir::new();
+    let helper = dir.write("helper.c", "int helper(void) { return 7; }");
+    let main = dir.write(
+        "main.c",
+        "int helper(void);\nint main(void) { return helper(); }",
+    );
+    run_driver(&["-c", &helper]).unwrap();
+    assert!(dir.0.join("helper.o").exists());
+    assert!(!dir.0.join("helper.s").exists());
+
+    let main_obj = dir.0.join("main.o");
+    let status = Command::new("gcc")
+        .args(["-c", &main, "-o"])
+        .arg(&main_obj)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = dir.0.join("prog");
+    let output = output.to_string_lossy();
+    run_driver(&[&helper, &main_obj.to_string_lossy(), "-o", &output]).unwrap();
+    let status = Command::new(dir.0.join("prog")).status().unwrap();
+    assert_eq!(status.code(), Some(7));
+}
+
+#[test]
+fn test_link_refuses_to_overwrite_input() {
+    let dir = ScratchDir::new();
+    let helper = dir.write("helper.c", "int helper(void) { return 7; }");
+    let main = dir.write(
+        "main.c",
+        "int helper(void);\nint main(void) { return helper(); }",
+    );
+    let object = dir.0.join("helper");
+    let status = Command::new("gcc")
+        .args(["-c", &helper, "-o"])
+        .arg(&object)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let contents = fs::read(&object).unwrap();
+
+    let err = run_driver(&[&object.to_string_lossy(), &main]).unwrap_err();
+    assert_eq!(err.exit_code(), 4);
+    assert!(err.to_string().contains("would overwrite an input file"));
+    assert_eq!(fs::read(&object).unwrap(), contents);
+    assert!(!dir.0.join("main.s").exists());
+}
+
+#[test]
+fn test_compile_error_reported_against_preprocessed_file() {
+    let dir = ScratchDir::new();
+    dir.write("header.h", "int helper(void);\nint other(void);\n");
+    let input = dir.write(
+        "prog.c",
+        "#include \"header.h\"\nint main(void) {\n    return\n}\n",
+    );
+    let err = run_driver(&[&input]).unwrap_err();
+    let preprocessed = dir.0.join("prog.i");
+    let source = fs::read_to_string(&preprocessed).unwrap();
+    let line = source.lines().position(|l| l.trim() == "}").unwrap() + 1;
+    assert!(err
+        .to_string()
+        .starts_with(&format!("{}:{}: ", preprocessed.display(), line)));
+}
@@ -0,0 +1,74 @@
+use super::*;
+use clap::Parser as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod control;
+mod functions;
+mod minimal;
+mod operators;
+mod storage;
+mod types;
+mod variables;
+
+#[cfg(test)]
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+/// A scratch directory that is removed when dropped
+#[cfg(test)]
+struct ScratchDir(PathBuf);
+
+#[cfg(test)]
+impl ScratchDir {
+    fn new() -> Self {
+        let n = NEXT_DIR.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("nqcc-test-{}-{}", std::process::id(), n));
+        fs::create_dir_all(&dir).expect("could not create scratch directory");
+        Self(dir)
+    }
+
+    fn write(&self, name: &str, contents: &str) -> String {
+        let path = self.0.join(name);
+        fs::write(&path, contents).expect("could not write source file");
+        path.to_string_lossy().into_owned()
+    }
+}
+
+#[cfg(test)]
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run the driver with the given command line arguments
+#[cfg(test)]
+fn run_driver(args: &[&str]) -> Result<(), DriverError> {
+    let args = Args::parse_from(std::iter::once("nqcc").chain(args.iter().copied()));
+    run(&args)
+}
+
+/// Compile and link a single C source file, run it and return its
+/// exit status
+#[cfg(test)]
+fn run_program(source: &str) -> i32 {
+    run_program_with_output(source).0
+}
+
+/// Compile and link a single C source file, run it and return its
+/// exit status along with what it wrote to stdout
+#[cfg(test)]
+fn run_program_with_output(source: &str) -> (i32, String) {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", source);
+    if let Err(e) = run_driver(&[&input]) {
+        panic!("compilation failed: {}", e);
+    }
+    let output = Command::new(dir.0.join("prog"))
+        .output()
+        .expect("could not run the compiled program");
+    let status = output
+        .status
+        .code()
+        .expect("program was killed by a signal");
+    (status, String::from_utf8_lossy(&output.stdout).into_owned())
+}
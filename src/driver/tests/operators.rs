@@ -0,0 +1,111 @@
+use super::*;
+
+#[test]
+fn test_negate() {
+    assert_eq!(run_program("int main(void) { return -(-7); }"), 7);
+    assert_eq!(run_program("int main(void) { return -1; }"), 255);
+}
+
+#[test]
+fn test_complement() {
+    assert_eq!(run_program("int main(void) { return ~(-13); }"), 12);
+    assert_eq!(run_program("int main(void) { return ~0; }"), 255);
+}
+
+#[test]
+fn test_logical_not() {
+    assert_eq!(run_program("int main(void) { return !5; }"), 0);
+    assert_eq!(run_program("int main(void) { return !0; }"), 1);
+}
+
+#[test]
+fn test_nested_unary() {
+    assert_eq!(run_program("int main(void) { return -~!5; }"), 1);
+    assert_eq!(run_program("int main(void) { return !-~-1; }"), 1);
+}
+
+#[test]
+fn test_arithmetic_precedence() {
+    assert_eq!(run_program("int main(void) { return 2 + 3 * 4; }"), 14);
+    assert_eq!(run_program("int main(void) { return 20 - 2 - 3; }"), 15);
+    assert_eq!(run_program("int main(void) { return 10 / 3 * 3; }"), 9);
+}
+
+#[test]
+fn test_nested_arithmetic() {
+    // (1 + 2) * 3 % 4 - 10 / 3 == -2
+    assert_eq!(
+        run_program("int main(void) { return (1 + 2) * 3 % 4 - 10 / 3; }"),
+        254
+    );
+}
+
+#[test]
+fn test_signed_division() {
+    assert_eq!(run_program("int main(void) { return -7 / 2 + 10; }"), 7);
+    assert_eq!(run_program("int main(void) { return -7 % 3 + 10; }"), 9);
+}
+
+#[test]
+fn test_arithmetic_with_unary() {
+    assert_eq!(
+        run_program("int main(void) { return ~2 + 3 * -4 + 100; }"),
+        85
+    );
+}
+
+#[test]
+fn test_bitwise_operators() {
+    assert_eq!(run_program("int main(void) { return 5 & 3; }"), 1);
+    assert_eq!(run_program("int main(void) { return 5 | 3; }"), 7);
+    assert_eq!(run_program("int main(void) { return 5 ^ 3; }"), 6);
+    assert_eq!(run_program("int main(void) { return 1 | 2 ^ 3 & 4; }"), 3);
+}
+
+#[test]
+fn test_shift_operators() {
+    assert_eq!(run_program("int main(void) { return 1 << 4; }"), 16);
+    assert_eq!(run_program("int main(void) { return -16 >> 2; }"), 252);
+    assert_eq!(run_program("int main(void) { return 1 << 2 + 1; }"), 8);
+}
+
+#[test]
+fn test_shift_by_computed_count() {
+    assert_eq!(
+        run_program("int main(void) { return (3 << 2) >> (1 + 1); }"),
+        3
+    );
+}
+
+#[test]
+fn test_relational_operators() {
+    assert_eq!(run_program("int main(void) { return 1 < 2; }"), 1);
+    assert_eq!(run_program("int main(void) { return 2 <= 1; }"), 0);
+    assert_eq!(run_program("int main(void) { return 3 > 3; }"), 0);
+    assert_eq!(run_program("int main(void) { return 3 >= 3; }"), 1);
+    assert_eq!(run_program("int main(void) { return -1 < 0; }"), 1);
+}
+
+#[test]
+fn test_equality_operators() {
+    assert_eq!(run_program("int main(void) { return 1 == 1; }"), 1);
+    assert_eq!(run_program("int main(void) { return 1 != 1; }"), 0);
+    assert_eq!(run_program("int main(void) { return 10 > 2 == 1; }"), 1);
+}
+
+#[test]
+fn test_logical_operators() {
+    assert_eq!(run_program("int main(void) { return 2 && 3; }"), 1);
+    assert_eq!(run_program("int main(void) { return 0 || 0; }"), 0);
+    assert_eq!(
+        run_program("int main(void) { return 1 == 2 || 3 < 4 && 5 != 5; }"),
+        0
+    );
+}
+
+#[test]
+fn test_short_circuit() {
+    // The right operand would trap if it were evaluated
+    assert_eq!(run_program("int main(void) { return 0 && 1 / 0; }"), 0);
+    assert_eq!(run_program("int main(void) { return 1 || 1 / 0; }"), 1);
+}
@@ -0,0 +1,100 @@
+use super::*;
+
+#[test]
+fn test_global_variables() {
+    let source = r#"
+        int counter;
+        int step = 3;
+
+        int bump(void) {
+            counter = counter + step;
+            return counter;
+        }
+
+        int main(void) {
+            bump();
+            bump();
+            return counter;
+        }
+    "#;
+    assert_eq!(run_program(source), 6);
+}
+
+#[test]
+fn test_static_local_keeps_its_value() {
+    let source = r#"
+        int next(void) {
+            static int n = 10;
+            n = n + 1;
+            return n;
+        }
+
+        int main(void) {
+            next();
+            next();
+            return next();
+        }
+    "#;
+    assert_eq!(run_program(source), 13);
+}
+
+#[test]
+fn test_tentative_definition_and_local_extern() {
+    let source = r#"
+        int x;
+        int read(void) {
+            extern int x;
+            return x;
+        }
+        int x = 7;
+        int main(void) {
+            int x = 1;
+            {
+                extern int x;
+                return read() + x;
+            }
+        }
+    "#;
+    assert_eq!(run_program(source), 14);
+}
+
+#[test]
+fn test_linkage_across_translation_units() {
+    let dir = ScratchDir::new();
+    let shared = dir.write(
+        "shared.c",
+        r#"
+            int shared = 40;
+            static int hidden = 100;
+            static int helper(void) { return hidden; }
+            int get_hidden(void) { return helper(); }
+        "#,
+    );
+    let main = dir.write(
+        "main.c",
+        r#"
+            extern int shared;
+            static int hidden = 1;
+            int get_hidden(void);
+            int main(void) { return shared + hidden + get_hidden(); }
+        "#,
+    );
+    let output = dir.0.join("prog");
+    run_driver(&[&main, &shared, "-o", output.to_str().unwrap()]).unwrap();
+    let status = Command::new(&output).status().unwrap();
+    assert_eq!(status.code(), Some(141));
+}
+
+#[test]
+fn test_conflicting_linkage_is_compile_error() {
+    let dir = ScratchDir::new();
+    let input = dir.write(
+        "prog.c",
+        "static int x;\nint x;\nint main(void) { return x; }\n",
+    );
+    let err = run_driver(&[&input, "-S"]).unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert!(err
+        .to_string()
+        .contains("prog.i:2: 'x' redeclared with different linkage"));
+}
@@ -0,0 +1,552 @@
+use super::*;
+
+#[test]
+fn test_long_arithmetic_beyond_32_bits() {
+    let source = r#"
+        int main(void) {
+            long big = 4294967296l;
+            long product = big * 3;
+            return product / big == 3 && product > 2147483647;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_large_long_constants() {
+    let source = r#"
+        long add(long a, long b) { return a + b; }
+        int main(void) {
+            long x = add(9223372036854775000, -9223372036854775000);
+            return x == 0 && 100000000000 - 99999999999 == 1;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_implicit_sign_extension() {
+    let source = r#"
+        int main(void) {
+            int negative = -5;
+            long wide = negative;
+            return wide + 4294967296l == 4294967291l;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_casts_truncate_and_extend() {
+    let source = r#"
+        int main(void) {
+            long big = 4294967298l;
+            int low = (int) big;
+            long back = (long) low;
+            return low + (int) back;
+        }
+    "#;
+    assert_eq!(run_program(source), 4);
+}
+
+#[test]
+fn test_long_parameters_and_return_values() {
+    let source = r#"
+        long sum(int a, long b, int c, long d, int e, long f, int g, long h) {
+            return a + b + c + d + e + f + g + h;
+        }
+        int main(void) {
+            long total = sum(1, 2, 3, 4, 5, 6, 7, 8589934592l);
+            return total - 8589934592l;
+        }
+    "#;
+    assert_eq!(run_program(source), 28);
+}
+
+#[test]
+fn test_return_value_converted_to_function_type() {
+    let source = r#"
+        int narrow(long x) { return x; }
+        long widen(int x) { return x; }
+        int main(void) {
+            return narrow(4294967303l) + (widen(-1) == -1l);
+        }
+    "#;
+    assert_eq!(run_program(source), 8);
+}
+
+#[test]
+fn test_static_long_variables() {
+    let source = r#"
+        long total = 8589934592;
+        static long zero;
+        long count(void) {
+            static long calls = 2;
+            calls = calls + 1;
+            return calls;
+        }
+        int main(void) {
+            count();
+            return (total / 4294967296) + count() + zero;
+        }
+    "#;
+    assert_eq!(run_program(source), 6);
+}
+
+#[test]
+fn test_long_shifts_and_division() {
+    let source = r#"
+        int main(void) {
+            long x = 1l << 40;
+            int shift = 38;
+            long remainder = -7l % 3;
+            return (x >> shift) + remainder;
+        }
+    "#;
+    assert_eq!(run_program(source), 3);
+}
+
+#[test]
+fn test_unsigned_wraparound_hash() {
+    // FNV-1a over a few bytes, relying on unsigned overflow
+    let source = r#"
+        unsigned int fnv1a(unsigned int hash, int byte) {
+            return (hash ^ byte) * 16777619u;
+        }
+        int main(void) {
+            unsigned int hash = 2166136261u;
+            hash = fnv1a(hash, 97);
+            hash = fnv1a(hash, 98);
+            hash = fnv1a(hash, 99);
+            return hash == 440920331u;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_unsigned_division_and_shifts() {
+    let source = r#"
+        int main(void) {
+            unsigned int big = 4294967295u;
+            unsigned long huge = 18446744073709551615ul;
+            int shifted = (big >> 28) == 15 && (huge >> 60) == 15;
+            int divided = big / 2u == 2147483647u && big % 10u == 5;
+            int long_divided = huge / 3ul == 6148914691236517205ul;
+            return shifted + divided + long_divided;
+        }
+    "#;
+    assert_eq!(run_program(source), 3);
+}
+
+#[test]
+fn test_unsigned_comparisons() {
+    let source = r#"
+        int main(void) {
+            unsigned int big = 4294967295u;
+            unsigned long zero = 0;
+            // -1 converts to the largest unsigned value
+            int a = big > 1u;
+            int b = -1 > 0u;
+            int c = zero < 18446744073709551615ul;
+            int d = 2147483648u >= 2147483647u;
+            return a + b + c + d;
+        }
+    "#;
+    assert_eq!(run_program(source), 4);
+}
+
+#[test]
+fn test_zero_and_sign_extension() {
+    let source = r#"
+        int main(void) {
+            unsigned int u = 4294967295u;
+            long widened = u;
+            int i = -1;
+            unsigned long sign_extended = i;
+            unsigned int truncated = (unsigned int) 4294967298ul;
+            return widened == 4294967295l
+                && sign_extended == 18446744073709551615ul
+                && truncated == 2
+                && (int) u == -1;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_unsigned_static_variables() {
+    let source = r#"
+        unsigned long counter = 18446744073709551615ul;
+        static unsigned int small = 4294967295u;
+        signed long int two = 2;
+        int main(void) {
+            counter = counter + 1;
+            small = small + 2;
+            return counter == 0 && small == 1 && two - 3 < 0;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_double_arithmetic() {
+    let source = r#"
+        int main(void) {
+            double a = 1e10;
+            double b = .5;
+            double c = a * b / 2.5E2 - 3.;
+            return c == 19999997.0 && 7 / 2.0 == 3.5 && -b < 0;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_double_conversions() {
+    let source = r#"
+        int main(void) {
+            double d = -3.9;
+            int truncated = d;
+            unsigned int big = 4294967295.0;
+            unsigned long huge = 17000000000000000000.0;
+            double from_ulong = 18446744073709551615ul;
+            double from_uint = 4294967295u;
+            return truncated == -3
+                && big == 4294967295u
+                && huge == 17000000000000000000ul
+                && from_ulong == 18446744073709551616.0
+                && from_uint == 4294967295.0
+                && (long) 1e18 == 1000000000000000000l;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_nan_comparisons() {
+    let source = r#"
+        int main(void) {
+            double nan = 0.0 / 0.0;
+            int count = 0;
+            count = count + (nan == nan) + (nan < 1.0) + (nan > 1.0);
+            count = count + (nan <= 1.0) + (nan >= 1.0);
+            // NaN is not zero, so it is true
+            if (nan && !(!nan) && nan != nan)
+                count = count + 10;
+            return count;
+        }
+    "#;
+    assert_eq!(run_program(source), 10);
+}
+
+#[test]
+fn test_double_arguments_and_return_values() {
+    let source = r#"
+        double mix(double a, int b, double c, double d, long e, double f, double g,
+                   double h, double i, double j, int k, double l) {
+            return a + b + c + d + e + f + g + h + i + j + k + l;
+        }
+
+        double half(double x) {
+            return x / 2;
+        }
+
+        int main(void) {
+            double total = mix(1.0, 2, 3.0, 4.0, 5, 6.0, 7.0, 8.0, 9.0, 10.0, 11, 12.0);
+            return total == 78.0 && half(5) == 2.5;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_static_doubles() {
+    let source = r#"
+        double scale = 2.5;
+        static double zero;
+        double small = 1e-300;
+        int main(void) {
+            double negative_zero = -zero;
+            scale = scale * 2;
+            return scale == 5.0 && small > zero && negative_zero == 0.0 && 1 / negative_zero < 0;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_call_gcc_compiled_code_with_doubles() {
+    let dir = ScratchDir::new();
+    let helper = dir.write(
+        "helper.c",
+        r#"
+            double weighted(int n, double a, long m, double b) {
+                return n * a + m * b;
+            }
+        "#,
+    );
+    let object = dir.0.join("helper.o");
+    let status = Command::new("gcc")
+        .arg("-c")
+        .arg(&helper)
+        .arg("-o")
+        .arg(&object)
+        .status()
+        .expect("could not run gcc");
+    assert!(status.success());
+    let input = dir.write(
+        "prog.c",
+        r#"
+            double weighted(int n, double a, long m, double b);
+            int main(void) {
+                return weighted(2, 1.5, 3, 10.0);
+            }
+        "#,
+    );
+    let output = dir.0.join("prog");
+    run_driver(&[
+        &input,
+        object.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ])
+    .unwrap();
+    let status = Command::new(&output).status().unwrap();
+    assert_eq!(status.code(), Some(33));
+}
+
+#[test]
+fn test_pointers() {
+    let source = r#"
+        int *identity(int *p) {
+            return p;
+        }
+
+        static long counter;
+
+        long *get_counter(void) {
+            return &counter;
+        }
+
+        int main(void) {
+            int x = 3;
+            int *p = &x;
+            int **pp = &p;
+            **pp = 10;
+            *identity(&x) = *p + 10;
+            *get_counter() = 5;
+            *get_counter() = *get_counter() + 1;
+            double d = 1.5;
+            double *dp = &d;
+            *dp = *dp * 2;
+            int *null = 0;
+            return x == 20 && counter == 6 && d == 3.0 && !null && &*p == p;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_pointer_conversions() {
+    let source = r#"
+        int main(void) {
+            int x = 7;
+            int *p = &x;
+            unsigned long address = (unsigned long) p;
+            int *q = (int *) address;
+            long *null = (long *) 0;
+            int *chosen = x > 5 ? p : 0;
+            return *q + (null == 0) + (chosen == p);
+        }
+    "#;
+    assert_eq!(run_program(source), 9);
+}
+
+#[test]
+fn test_pointer_type_errors() {
+    let source = "int main(void) { int x; long *p = &x; return 0; }";
+    let err = compile(source, "prog.i", Stage::Emit).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "prog.i:1: incompatible types 'int *' and 'long *'\n1 error(s) found"
+    );
+}
+
+#[test]
+fn test_arrays() {
+    let source = r#"
+        long totals[3] = {1, 2};
+        static int grid[2][3] = {{1, 2, 3}, {4, 5, 6}};
+        int zeros[10];
+
+        int sum(int *values, int n) {
+            int total = 0;
+            for (int i = 0; i < n; i = i + 1)
+                total = total + values[i];
+            return total;
+        }
+
+        int sum_rows(int rows[2][3]) {
+            int total = 0;
+            for (int i = 0; i < 2; i = i + 1)
+                for (int j = 0; j < 3; j = j + 1)
+                    total = total + rows[i][j];
+            return total;
+        }
+
+        int main(void) {
+            int a[5] = {1, 2, 3};
+            int local[2][3] = {{1, 2, 3}, {4}};
+            double d[2][2] = {{1.5}, {2.5, 3.5}};
+            long *p = totals;
+            a[4] = 10;
+            zeros[9] = 7;
+            int (*row)[3] = local + 1;
+            return sum(a, 5) == 16 && p[1] == 2 && totals[2] == 0 && 4[a] == 10
+                && sum_rows(local) == 10 && sum_rows(grid) == 21
+                && d[0][1] == 0.0 && d[1][1] == 3.5 && (*row)[0] == 4
+                && zeros[9] + zeros[0] == 7;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_pointer_arithmetic() {
+    let source = r#"
+        int main(void) {
+            long a[3][5][7];
+            int b[4] = {1, 2, 3, 4};
+            int *first = b;
+            int *last = b + 3;
+            long i = 2;
+            unsigned int k = 1;
+            a[i][4][6] = 99;
+            return (last - first) * 10 + (first - last + 3)
+                + (*(last - k) == 3) + (first < last) + (last >= b + 4)
+                + (&a[1] + 1 == &a[2]) + (a[2][4][6] == 99);
+        }
+    "#;
+    assert_eq!(run_program(source), 34);
+}
+
+#[test]
+fn test_array_type_errors() {
+    let source = "int main(void) { int a[2] = {1, 2, 3}; return 0; }";
+    let err = compile(source, "prog.i", Stage::Emit).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "prog.i:1: too many elements in initializer for 'int[2]'\n1 error(s) found"
+    );
+}
+
+#[test]
+fn test_chars() {
+    let source = r#"
+        int main(void) {
+            char c = 'A';
+            return c + 1;
+        }
+    "#;
+    assert_eq!(run_program(source), 66);
+}
+
+#[test]
+fn test_char_conversions() {
+    let source = r#"
+        unsigned char wrapped = 300;
+        char letters[4] = {'a', '\n', '\x41', '\101'};
+        int sum(char a, unsigned char b, signed char c, int d, int e, int f,
+                char g, unsigned char h) {
+            return a + b + c + g + h;
+        }
+        char low_byte(long l) { return l; }
+        int main(void) {
+            unsigned char u = 255;
+            char s = 200;
+            double d = s;
+            unsigned char from_double = 250.7;
+            u = u + 1;
+            return u == 0 && s == -56 && wrapped == 44 && d == -56.0
+                && from_double == 250 && ~s == 55 && (s << 1) == -112
+                && letters[1] == 10 && letters[2] == letters[3]
+                && '\\' == 92 && '\'' == 39 && '\0' == 0 && '\377' == -1
+                && sum(1, 255, -1, 0, 0, 0, 2, 250) == 507
+                && low_byte(321) == 'A';
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_malformed_char_constant() {
+    let source = "int main(void) { return 'ab'; }";
+    let err = compile(source, "prog.i", Stage::Emit).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "prog.i:1: Multi-character constants are not supported\n1 error(s) found"
+    );
+}
+
+#[test]
+fn test_strings() {
+    let source = r#"
+        int puts(char *s);
+        char *greeting = "hello, " "world";
+        char padded[8] = "abc";
+        char table[2][4] = {"one", "two"};
+        int length(char *s) {
+            int n = 0;
+            while (s[n]) n = n + 1;
+            return n;
+        }
+        int main(void) {
+            char local[] = "\tx\x41\101\0y";
+            static char *word = "static";
+            unsigned char bytes[3] = "\377";
+            puts(greeting);
+            return length(greeting) == 12 && length(padded) == 3
+                && padded[7] == 0 && table[1][2] == 'o'
+                && local[0] == 9 && local[2] == local[3]
+                && local[4] == 0 && local[5] == 'y' && "abc"[2] == 'c'
+                && length(word) == 6 && bytes[0] == 255;
+        }
+    "#;
+    assert_eq!(run_program(source), 1);
+}
+
+#[test]
+fn test_string_too_long() {
+    let source = "int main(void) { char s[3] = \"abc\\0\"; return 0; }";
+    let err = compile(source, "prog.i", Stage::Emit).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "prog.i:1: initializer-string for 'char[3]' is too long\n1 error(s) found"
+    );
+}
+
+#[test]
+fn test_extern_array_without_size() {
+    let source = r#"
+        extern int a[];
+        int third(void) { return a[2]; }
+        int a[2 * 2] = {1, 2, 3};
+        int main(void) {
+            extern int a[];
+            return third() * 10 + a[3] + a[0];
+        }
+    "#;
+    assert_eq!(run_program(source), 31);
+
+    let dir = ScratchDir::new();
+    let table = dir.write("table.c", "long table[3] = {4, 5, 6};");
+    let input = dir.write(
+        "prog.c",
+        "extern long table[];\nint main(void) { return table[0] + table[2]; }",
+    );
+    let output = dir.0.join("prog");
+    run_driver(&[&input, &table, "-o", output.to_str().unwrap()]).unwrap();
+    let status = Command::new(&output).status().unwrap();
+    assert_eq!(status.code(), Some(10));
+}
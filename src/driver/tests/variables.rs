@@ -0,0 +1,107 @@
+use super::*;
+
+#[test]
+fn test_local_variables() {
+    let source = r#"
+        int main(void) {
+            int a = 3;
+            int b;
+            b = a * 4;
+            return a + b;
+        }
+    "#;
+    assert_eq!(run_program(source), 15);
+}
+
+#[test]
+fn test_chained_assignment() {
+    let source = r#"
+        int main(void) {
+            int a;
+            int b;
+            a = b = 7;
+            return a + b;
+        }
+    "#;
+    assert_eq!(run_program(source), 14);
+}
+
+#[test]
+fn test_assignment_in_initializer() {
+    assert_eq!(
+        run_program("int main(void) { int a = a = 5; return a; }"),
+        5
+    );
+}
+
+#[test]
+fn test_missing_return() {
+    assert_eq!(run_program("int main(void) { int a = 4; a = a + 1; }"), 0);
+}
+
+#[test]
+fn test_short_circuit_skips_assignment() {
+    let source = r#"
+        int main(void) {
+            int a = 0;
+            0 && (a = 5);
+            1 || (a = 6);
+            return a;
+        }
+    "#;
+    assert_eq!(run_program(source), 0);
+}
+
+#[test]
+fn test_semantic_error_reports_line() {
+    let dir = ScratchDir::new();
+    let input = dir.write("prog.c", "int main(void) {\n    return x;\n}\n");
+    let err = run_driver(&[&input]).unwrap_err();
+    assert_eq!(err.exit_code(), 3);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "{}:2: use of undeclared variable 'x'\n1 error(s) found",
+            dir.0.join("prog.i").display()
+        )
+    );
+}
+
+#[test]
+fn test_block_scoping() {
+    let source = r#"
+        int main(void) {
+            int x = 1;
+            int y = 0;
+            {
+                int x = 10;
+                y = y + x;
+                {
+                    x = x + 1;
+                    int x = 100;
+                    y = y + x;
+                }
+                y = y + x;
+            }
+            return y + x;
+        }
+    "#;
+    // 10 + 100 + 11 + 1
+    assert_eq!(run_program(source), 122);
+}
+
+#[test]
+fn test_block_in_if() {
+    let source = r#"
+        int main(void) {
+            int a = 3;
+            if (a) {
+                int a = 4;
+                return a;
+            } else {
+                return 0;
+            }
+        }
+    "#;
+    assert_eq!(run_program(source), 4);
+}
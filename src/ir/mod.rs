@@ -0,0 +1,753 @@
+pub mod tacky;
+#[cfg(test)]
+mod tests;
+
+use crate::ir::tacky::*;
+use crate::parser::ast;
+use crate::semantic::symbols::{IdentifierAttrs, InitialValue, StaticInit, Symbol, SymbolTable};
+
+/// Lower the C AST to the three-address IR. Only function definitions
+/// produce code; declarations without a body are dropped. Variables with
+/// static storage duration are defined from the symbol table, since
+/// their initial values may come from any of their declarations.
+/// Temporaries are added to the symbol table so that later passes know
+/// their types.
+pub fn generate(program: &ast::Program, symbols: &mut SymbolTable) -> Program {
+    // Labels end up in a single assembly file, so the counter that makes
+    // them unique is shared by all functions
+    let mut generator = Generator::new(symbols);
+    let mut top_level: Vec<TopLevel> = program
+        .declarations
+        .iter()
+        .filter_map(|decl| match decl {
+            ast::Declaration::Function(function) => generator.function(function),
+            ast::Declaration::Variable(_) => None,
+        })
+        .map(TopLevel::Function)
+        .collect();
+    top_level.extend(static_variables(symbols).map(TopLevel::StaticVariable));
+    top_level.extend(static_constants(symbols).map(TopLevel::StaticConstant));
+    Program { top_level }
+}
+
+fn static_constants(symbols: &SymbolTable) -> impl Iterator<Item = StaticConstant> + '_ {
+    symbols
+        .iter()
+        .filter_map(|(name, symbol)| match &symbol.attrs {
+            IdentifierAttrs::Constant(init) => Some(StaticConstant {
+                name: name.to_string(),
+                init: init.clone(),
+            }),
+            _ => None,
+        })
+}
+
+fn static_variables(symbols: &SymbolTable) -> impl Iterator<Item = StaticVariable> + '_ {
+    symbols.iter().filter_map(|(name, symbol)| {
+        let IdentifierAttrs::Static { init, global } = &symbol.attrs else {
+            return None;
+        };
+        let init = match init {
+            InitialValue::Initial(values) => values.clone(),
+            InitialValue::Tentative => vec![StaticInit::zero(&symbol.ty)],
+            InitialValue::NoInitializer => return None,
+        };
+        Some(StaticVariable {
+            name: name.to_string(),
+            global: *global,
+            init,
+        })
+    })
+}
+
+struct Generator<'a> {
+    symbols: &'a mut SymbolTable,
+    function_name: String,
+    counter: usize,
+    instructions: Vec<Instruction>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbols: &'a mut SymbolTable) -> Self {
+        Generator {
+            symbols,
+            function_name: String::new(),
+            counter: 0,
+            instructions: Vec::new(),
+        }
+    }
+
+    fn function(&mut self, function: &ast::FunctionDeclaration) -> Option<Function> {
+        let body = function.body.as_ref()?;
+        let global = match self.symbols.get(&function.name).map(|symbol| &symbol.attrs) {
+            Some(IdentifierAttrs::Function { global, .. }) => *global,
+            _ => unreachable!("functions are in the symbol table"),
+        };
+        self.function_name = function.name.clone();
+        self.block(body);
+        // Falling off the end of main returns 0, and for other functions
+        // the result is undefined, so returning 0 is always safe
+        let ast::Type::Function { ret, .. } = &function.fun_type else {
+            unreachable!("function declarations have function types");
+        };
+        self.emit(Instruction::Return(Val::Constant(
+            ast::Const::Int(0).convert_to(ret),
+        )));
+        Some(Function {
+            name: function.name.clone(),
+            global,
+            params: function.params.clone(),
+            body: std::mem::take(&mut self.instructions),
+        })
+    }
+
+    fn block(&mut self, block: &ast::Block) {
+        for item in &block.0 {
+            match item {
+                ast::BlockItem::Statement(stmt) => self.statement(stmt),
+                ast::BlockItem::Declaration(ast::Declaration::Variable(decl)) => {
+                    self.declaration(decl)
+                }
+                // Local function declarations only matter to semantic analysis
+                ast::BlockItem::Declaration(ast::Declaration::Function(_)) => {}
+            }
+        }
+    }
+
+    fn declaration(&mut self, decl: &ast::VariableDeclaration) {
+        // Static and extern variables are not initialized at run time
+        if decl.storage_class.is_some() {
+            return;
+        }
+        match &decl.init {
+            Some(ast::Initializer::Single(exp)) if !decl.var_type.is_array() => {
+                let src = self.expression(exp);
+                self.emit(Instruction::Copy {
+                    src,
+                    dst: Val::Var(decl.name.clone()),
+                });
+            }
+            Some(init) => self.initialize(&decl.name, init, &decl.var_type, 0),
+            None => {}
+        }
+    }
+
+    /// Initialize the part of the array `name` of type `ty` that starts
+    /// `offset` bytes from its beginning, one scalar at a time
+    fn initialize(&mut self, name: &str, init: &ast::Initializer, ty: &ast::Type, offset: usize) {
+        match (init, ty) {
+            (ast::Initializer::Single(exp), ast::Type::Array(_, count)) => {
+                let ast::ExpressionKind::String(bytes) = &exp.kind else {
+                    unreachable!("arrays are only initialized by lists and string literals");
+                };
+                self.initialize_string(name, bytes, *count, offset);
+            }
+            (ast::Initializer::Single(exp), _) => {
+                let src = self.expression(exp);
+                self.emit(Instruction::CopyToOffset {
+                    src,
+                    dst: name.to_string(),
+                    offset,
+                });
+            }
+            (ast::Initializer::Compound(items, _), ast::Type::Array(element, _)) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.initialize(name, item, element, offset + i * element.size());
+                }
+            }
+            _ => unreachable!("compound initializers are only for arrays"),
+        }
+    }
+
+    /// Copy a string literal into the first `count` bytes of a char array
+    /// at `offset`, eight bytes at a time while they last. The rest of
+    /// the array, starting with the terminating null, is zero.
+    fn initialize_string(&mut self, name: &str, bytes: &[u8], count: usize, offset: usize) {
+        let mut padded = bytes.to_vec();
+        padded.resize(count, 0);
+        let mut position = 0;
+        while position < count {
+            let (value, width) = match padded.get(position..position + 8) {
+                Some(chunk) => (
+                    ast::Const::Long(i64::from_le_bytes(chunk.try_into().unwrap())),
+                    8,
+                ),
+                None => (ast::Const::Char(padded[position] as i8), 1),
+            };
+            self.emit(Instruction::CopyToOffset {
+                src: Val::Constant(value),
+                dst: name.to_string(),
+                offset: offset + position,
+            });
+            position += width;
+        }
+    }
+
+    fn statement(&mut self, stmt: &ast::Statement) {
+        match stmt {
+            ast::Statement::Return(exp) => {
+                let val = self.expression(exp);
+                self.emit(Instruction::Return(val));
+            }
+            ast::Statement::Expression(exp) => {
+                self.expression(exp);
+            }
+            ast::Statement::If(condition, then, None) => {
+                let end_label = self.make_label("if_end");
+                let c = self.condition(condition);
+                self.emit(Instruction::JumpIfZero(c, end_label.clone()));
+                self.statement(then);
+                self.emit(Instruction::Label(end_label));
+            }
+            ast::Statement::If(condition, then, Some(otherwise)) => {
+                let else_label = self.make_label("if_else");
+                let end_label = self.make_label("if_end");
+                let c = self.condition(condition);
+                self.emit(Instruction::JumpIfZero(c, else_label.clone()));
+                self.statement(then);
+                self.emit(Instruction::Jump(end_label.clone()));
+                self.emit(Instruction::Label(else_label));
+                self.statement(otherwise);
+                self.emit(Instruction::Label(end_label));
+            }
+            ast::Statement::Compound(block) => self.block(block),
+            ast::Statement::Break { label, .. } => {
+                self.emit(Instruction::Jump(break_label(label)));
+            }
+            ast::Statement::Continue { label, .. } => {
+                self.emit(Instruction::Jump(continue_label(label)));
+            }
+            ast::Statement::While {
+                condition,
+                body,
+                label,
+            } => {
+                self.emit(Instruction::Label(continue_label(label)));
+                let c = self.condition(condition);
+                self.emit(Instruction::JumpIfZero(c, break_label(label)));
+                self.statement(body);
+                self.emit(Instruction::Jump(continue_label(label)));
+                self.emit(Instruction::Label(break_label(label)));
+            }
+            ast::Statement::DoWhile {
+                body,
+                condition,
+                label,
+            } => {
+                self.emit(Instruction::Label(start_label(label)));
+                self.statement(body);
+                self.emit(Instruction::Label(continue_label(label)));
+                let c = self.condition(condition);
+                self.emit(Instruction::JumpIfNotZero(c, start_label(label)));
+                self.emit(Instruction::Label(break_label(label)));
+            }
+            ast::Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                label,
+            } => {
+                match init.as_ref() {
+                    ast::ForInit::Declaration(decl) => self.declaration(decl),
+                    ast::ForInit::Expression(Some(exp)) => {
+                        self.expression(exp);
+                    }
+                    ast::ForInit::Expression(None) => {}
+                }
+                self.emit(Instruction::Label(start_label(label)));
+                // A missing condition is always true
+                if let Some(condition) = condition {
+                    let c = self.condition(condition);
+                    self.emit(Instruction::JumpIfZero(c, break_label(label)));
+                }
+                self.statement(body);
+                self.emit(Instruction::Label(continue_label(label)));
+                if let Some(post) = post {
+                    self.expression(post);
+                }
+                self.emit(Instruction::Jump(start_label(label)));
+                self.emit(Instruction::Label(break_label(label)));
+            }
+            ast::Statement::Null => {}
+        }
+    }
+
+    fn expression(&mut self, exp: &ast::Expression) -> Val {
+        match &exp.kind {
+            ast::ExpressionKind::Constant(value) => Val::Constant(*value),
+            ast::ExpressionKind::String(_) => {
+                unreachable!("string literals are replaced by their labels during type checking")
+            }
+            ast::ExpressionKind::Var(name) => Val::Var(name.clone()),
+            ast::ExpressionKind::Cast(ty, operand) => {
+                let src = self.expression(operand);
+                if ty == operand.ty() {
+                    return src;
+                }
+                if let Val::Constant(value) = src {
+                    return Val::Constant(value.convert_to(ty));
+                }
+                let dst = self.make_temporary(ty);
+                self.convert(src, operand.ty(), ty, &dst);
+                dst
+            }
+            ast::ExpressionKind::Assignment(left, right) => {
+                if let ast::ExpressionKind::Var(name) = &left.kind {
+                    let src = self.expression(right);
+                    let dst = Val::Var(name.clone());
+                    self.emit(Instruction::Copy {
+                        src,
+                        dst: dst.clone(),
+                    });
+                    return dst;
+                }
+                let dst_ptr = self.address(left);
+                let src = self.expression(right);
+                self.emit(Instruction::Store {
+                    src: src.clone(),
+                    dst_ptr,
+                });
+                src
+            }
+            ast::ExpressionKind::Dereference(_) | ast::ExpressionKind::Subscript(..) => {
+                let src_ptr = self.address(exp);
+                let dst = self.make_temporary(exp.ty());
+                self.emit(Instruction::Load {
+                    src_ptr,
+                    dst: dst.clone(),
+                });
+                dst
+            }
+            ast::ExpressionKind::AddressOf(operand) => self.address(operand),
+            // There is no instruction to test a double against zero, so
+            // compare it instead
+            ast::ExpressionKind::Unary(ast::UnaryOperator::Not, operand)
+                if *operand.ty() == ast::Type::Double =>
+            {
+                let src = self.expression(operand);
+                let dst = self.make_temporary(&ast::Type::Int);
+                self.emit(Instruction::Binary {
+                    op: BinaryOperator::Equal,
+                    src1: src,
+                    src2: Val::Constant(ast::Const::Double(0.0)),
+                    dst: dst.clone(),
+                });
+                dst
+            }
+            ast::ExpressionKind::Unary(op, operand) => {
+                let src = self.expression(operand);
+                let dst = self.make_temporary(exp.ty());
+                self.emit(Instruction::Unary {
+                    op: unary_operator(*op),
+                    src,
+                    dst: dst.clone(),
+                });
+                dst
+            }
+            ast::ExpressionKind::Conditional(condition, then, otherwise) => {
+                let else_label = self.make_label("cond_else");
+                let end_label = self.make_label("cond_end");
+                let dst = self.make_temporary(exp.ty());
+                let c = self.condition(condition);
+                self.emit(Instruction::JumpIfZero(c, else_label.clone()));
+                let v1 = self.expression(then);
+                self.emit(Instruction::Copy {
+                    src: v1,
+                    dst: dst.clone(),
+                });
+                self.emit(Instruction::Jump(end_label.clone()));
+                self.emit(Instruction::Label(else_label));
+                let v2 = self.expression(otherwise);
+                self.emit(Instruction::Copy {
+                    src: v2,
+                    dst: dst.clone(),
+                });
+                self.emit(Instruction::Label(end_label));
+                dst
+            }
+            ast::ExpressionKind::FunctionCall(name, args) => {
+                let args = args.iter().map(|arg| self.expression(arg)).collect();
+                let dst = self.make_temporary(exp.ty());
+                self.emit(Instruction::FunCall {
+                    name: name.clone(),
+                    args,
+                    dst: dst.clone(),
+                });
+                dst
+            }
+            ast::ExpressionKind::Binary(ast::BinaryOperator::And, left, right) => {
+                self.logical_and(left, right)
+            }
+            ast::ExpressionKind::Binary(ast::BinaryOperator::Or, left, right) => {
+                self.logical_or(left, right)
+            }
+            ast::ExpressionKind::Binary(
+                op @ (ast::BinaryOperator::Add | ast::BinaryOperator::Subtract),
+                left,
+                right,
+            ) if left.ty().is_pointer() || right.ty().is_pointer() => {
+                self.pointer_arithmetic(*op, left, right)
+            }
+            ast::ExpressionKind::Binary(op, left, right) => {
+                let src1 = self.expression(left);
+                let src2 = self.expression(right);
+                let dst = self.make_temporary(exp.ty());
+                self.emit(Instruction::Binary {
+                    op: binary_operator(*op),
+                    src1,
+                    src2,
+                    dst: dst.clone(),
+                });
+                dst
+            }
+        }
+    }
+
+    /// Evaluate the address of an lvalue
+    fn address(&mut self, exp: &ast::Expression) -> Val {
+        match &exp.kind {
+            ast::ExpressionKind::Var(name) => {
+                let dst = self.make_temporary(&ast::Type::Pointer(Box::new(exp.ty().clone())));
+                self.emit(Instruction::GetAddress {
+                    src: Val::Var(name.clone()),
+                    dst: dst.clone(),
+                });
+                dst
+            }
+            // The address of *p is p itself
+            ast::ExpressionKind::Dereference(pointer) => self.expression(pointer),
+            // a[i] is *(a + i), where either operand may be the pointer
+            ast::ExpressionKind::Subscript(left, right) => {
+                let (pointer, index) = if left.ty().is_pointer() {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                let ptr = self.expression(pointer);
+                let index = self.expression(index);
+                self.add_to_pointer(ptr, index, pointer.ty())
+            }
+            _ => unreachable!("invalid lvalues are rejected during semantic analysis"),
+        }
+    }
+
+    /// Evaluate an addition or subtraction with a pointer operand. The
+    /// integer operand counts elements of the referenced type, and so
+    /// does the difference between two pointers.
+    fn pointer_arithmetic(
+        &mut self,
+        op: ast::BinaryOperator,
+        left: &ast::Expression,
+        right: &ast::Expression,
+    ) -> Val {
+        let src1 = self.expression(left);
+        let src2 = self.expression(right);
+        match op {
+            ast::BinaryOperator::Add if left.ty().is_pointer() => {
+                self.add_to_pointer(src1, src2, left.ty())
+            }
+            ast::BinaryOperator::Add => self.add_to_pointer(src2, src1, right.ty()),
+            ast::BinaryOperator::Subtract if right.ty().is_pointer() => {
+                let ast::Type::Pointer(referenced) = left.ty() else {
+                    unreachable!("only pointers are subtracted from pointers");
+                };
+                let difference = self.make_temporary(&ast::Type::Long);
+                self.emit(Instruction::Binary {
+                    op: BinaryOperator::Subtract,
+                    src1,
+                    src2,
+                    dst: difference.clone(),
+                });
+                let dst = self.make_temporary(&ast::Type::Long);
+                self.emit(Instruction::Binary {
+                    op: BinaryOperator::Divide,
+                    src1: difference,
+                    src2: Val::Constant(ast::Const::Long(referenced.size() as i64)),
+                    dst: dst.clone(),
+                });
+                dst
+            }
+            _ => {
+                let index = match src2 {
+                    Val::Constant(ast::Const::Long(value)) => {
+                        Val::Constant(ast::Const::Long(value.wrapping_neg()))
+                    }
+                    src => {
+                        let negated = self.make_temporary(&ast::Type::Long);
+                        self.emit(Instruction::Unary {
+                            op: UnaryOperator::Negate,
+                            src,
+                            dst: negated.clone(),
+                        });
+                        negated
+                    }
+                };
+                self.add_to_pointer(src1, index, left.ty())
+            }
+        }
+    }
+
+    /// Add `index` elements to the pointer `ptr` of type `ty`
+    fn add_to_pointer(&mut self, ptr: Val, index: Val, ty: &ast::Type) -> Val {
+        let ast::Type::Pointer(referenced) = ty else {
+            unreachable!("only pointers are indexed");
+        };
+        let dst = self.make_temporary(ty);
+        self.emit(Instruction::AddPtr {
+            ptr,
+            index,
+            scale: referenced.size(),
+            dst: dst.clone(),
+        });
+        dst
+    }
+
+    /// Evaluate an expression whose value is tested against zero. The
+    /// conditional jumps only test integers, so a double is compared
+    /// with zero first, which also makes NaN count as true.
+    fn condition(&mut self, exp: &ast::Expression) -> Val {
+        let val = self.expression(exp);
+        if *exp.ty() != ast::Type::Double {
+            return val;
+        }
+        let dst = self.make_temporary(&ast::Type::Int);
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::NotEqual,
+            src1: val,
+            src2: Val::Constant(ast::Const::Double(0.0)),
+            dst: dst.clone(),
+        });
+        dst
+    }
+
+    /// Convert `src` from type `from` to type `to`, storing the result
+    /// in `dst`
+    fn convert(&mut self, src: Val, from: &ast::Type, to: &ast::Type, dst: &Val) {
+        let dst = dst.clone();
+        let instruction = match (from, to) {
+            (ast::Type::Double, ast::Type::ULong) => return self.double_to_ulong(src, dst),
+            (ast::Type::ULong, ast::Type::Double) => return self.ulong_to_double(src, dst),
+            (ast::Type::Double, _) => Instruction::DoubleToInt { src, dst },
+            (_, ast::Type::Double) => Instruction::IntToDouble { src, dst },
+            // Between types of the same size only the interpretation of
+            // the bits changes
+            _ if from.size() == to.size() => Instruction::Copy { src, dst },
+            _ if from.size() > to.size() => Instruction::Truncate { src, dst },
+            _ if from.is_signed() => Instruction::SignExtend { src, dst },
+            _ => Instruction::ZeroExtend { src, dst },
+        };
+        self.emit(instruction);
+    }
+
+    /// Convert a double to unsigned long. The hardware conversion is
+    /// signed, so values of 2^63 and above are brought into the signed
+    /// range first and the top bit is added back afterwards.
+    fn double_to_ulong(&mut self, src: Val, dst: Val) {
+        let large_label = self.make_label("ulong_large");
+        let end_label = self.make_label("ulong_end");
+        let upper_bound = Val::Constant(ast::Const::Double(9223372036854775808.0));
+        let large = self.make_temporary(&ast::Type::Int);
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::GreaterOrEqual,
+            src1: src.clone(),
+            src2: upper_bound.clone(),
+            dst: large.clone(),
+        });
+        self.emit(Instruction::JumpIfNotZero(large, large_label.clone()));
+        self.emit(Instruction::DoubleToInt {
+            src: src.clone(),
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Jump(end_label.clone()));
+        self.emit(Instruction::Label(large_label));
+        let reduced = self.make_temporary(&ast::Type::Double);
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::Subtract,
+            src1: src,
+            src2: upper_bound,
+            dst: reduced.clone(),
+        });
+        self.emit(Instruction::DoubleToInt {
+            src: reduced,
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::Add,
+            src1: dst.clone(),
+            src2: Val::Constant(ast::Const::ULong(1 << 63)),
+            dst,
+        });
+        self.emit(Instruction::Label(end_label));
+    }
+
+    /// Convert an unsigned long to double. Values of 2^63 and above are
+    /// halved before the signed conversion and doubled afterwards. The
+    /// lowest bit is kept so that the result still rounds correctly.
+    fn ulong_to_double(&mut self, src: Val, dst: Val) {
+        let large_label = self.make_label("ulong_large");
+        let end_label = self.make_label("ulong_end");
+        let large = self.make_temporary(&ast::Type::Int);
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::GreaterThan,
+            src1: src.clone(),
+            src2: Val::Constant(ast::Const::ULong(i64::MAX as u64)),
+            dst: large.clone(),
+        });
+        self.emit(Instruction::JumpIfNotZero(large, large_label.clone()));
+        self.emit(Instruction::IntToDouble {
+            src: src.clone(),
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Jump(end_label.clone()));
+        self.emit(Instruction::Label(large_label));
+        let half = self.make_temporary(&ast::Type::ULong);
+        let low_bit = self.make_temporary(&ast::Type::ULong);
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::ShiftRight,
+            src1: src.clone(),
+            src2: Val::Constant(ast::Const::ULong(1)),
+            dst: half.clone(),
+        });
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::BitwiseAnd,
+            src1: src,
+            src2: Val::Constant(ast::Const::ULong(1)),
+            dst: low_bit.clone(),
+        });
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::BitwiseOr,
+            src1: half.clone(),
+            src2: low_bit,
+            dst: half.clone(),
+        });
+        self.emit(Instruction::IntToDouble {
+            src: half,
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Binary {
+            op: BinaryOperator::Add,
+            src1: dst.clone(),
+            src2: dst.clone(),
+            dst,
+        });
+        self.emit(Instruction::Label(end_label));
+    }
+
+    /// Evaluate `left && right`, skipping `right` if `left` is zero
+    fn logical_and(&mut self, left: &ast::Expression, right: &ast::Expression) -> Val {
+        let false_label = self.make_label("and_false");
+        let end_label = self.make_label("and_end");
+        let dst = self.make_temporary(&ast::Type::Int);
+        let v1 = self.condition(left);
+        self.emit(Instruction::JumpIfZero(v1, false_label.clone()));
+        let v2 = self.condition(right);
+        self.emit(Instruction::JumpIfZero(v2, false_label.clone()));
+        self.emit(Instruction::Copy {
+            src: Val::Constant(ast::Const::Int(1)),
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Jump(end_label.clone()));
+        self.emit(Instruction::Label(false_label));
+        self.emit(Instruction::Copy {
+            src: Val::Constant(ast::Const::Int(0)),
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Label(end_label));
+        dst
+    }
+
+    /// Evaluate `left || right`, skipping `right` if `left` is non-zero
+    fn logical_or(&mut self, left: &ast::Expression, right: &ast::Expression) -> Val {
+        let true_label = self.make_label("or_true");
+        let end_label = self.make_label("or_end");
+        let dst = self.make_temporary(&ast::Type::Int);
+        let v1 = self.condition(left);
+        self.emit(Instruction::JumpIfNotZero(v1, true_label.clone()));
+        let v2 = self.condition(right);
+        self.emit(Instruction::JumpIfNotZero(v2, true_label.clone()));
+        self.emit(Instruction::Copy {
+            src: Val::Constant(ast::Const::Int(0)),
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Jump(end_label.clone()));
+        self.emit(Instruction::Label(true_label));
+        self.emit(Instruction::Copy {
+            src: Val::Constant(ast::Const::Int(1)),
+            dst: dst.clone(),
+        });
+        self.emit(Instruction::Label(end_label));
+        dst
+    }
+
+    /// Create a new label with the given prefix
+    fn make_label(&mut self, prefix: &str) -> String {
+        let label = format!("{}.{}", prefix, self.counter);
+        self.counter += 1;
+        label
+    }
+
+    /// Create a new temporary variable. The name contains a '.', which
+    /// cannot appear in a C identifier, so it never clashes with a user
+    /// defined name.
+    fn make_temporary(&mut self, ty: &ast::Type) -> Val {
+        let name = format!("{}.tmp.{}", self.function_name, self.counter);
+        self.counter += 1;
+        self.symbols.insert(
+            &name,
+            Symbol {
+                ty: ty.clone(),
+                attrs: IdentifierAttrs::Local,
+            },
+        );
+        Val::Var(name)
+    }
+
+    fn emit(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+}
+
+fn start_label(loop_label: &str) -> String {
+    format!("start_{}", loop_label)
+}
+
+fn continue_label(loop_label: &str) -> String {
+    format!("continue_{}", loop_label)
+}
+
+fn break_label(loop_label: &str) -> String {
+    format!("break_{}", loop_label)
+}
+
+fn unary_operator(op: ast::UnaryOperator) -> UnaryOperator {
+    match op {
+        ast::UnaryOperator::Complement => UnaryOperator::Complement,
+        ast::UnaryOperator::Negate => UnaryOperator::Negate,
+        ast::UnaryOperator::Not => UnaryOperator::Not,
+    }
+}
+
+fn binary_operator(op: ast::BinaryOperator) -> BinaryOperator {
+    match op {
+        ast::BinaryOperator::Add => BinaryOperator::Add,
+        ast::BinaryOperator::Subtract => BinaryOperator::Subtract,
+        ast::BinaryOperator::Multiply => BinaryOperator::Multiply,
+        ast::BinaryOperator::Divide => BinaryOperator::Divide,
+        ast::BinaryOperator::Remainder => BinaryOperator::Remainder,
+        ast::BinaryOperator::BitwiseAnd => BinaryOperator::BitwiseAnd,
+        ast::BinaryOperator::BitwiseOr => BinaryOperator::BitwiseOr,
+        ast::BinaryOperator::BitwiseXor => BinaryOperator::BitwiseXor,
+        ast::BinaryOperator::ShiftLeft => BinaryOperator::ShiftLeft,
+        ast::BinaryOperator::ShiftRight => BinaryOperator::ShiftRight,
+        ast::BinaryOperator::Equal => BinaryOperator::Equal,
+        ast::BinaryOperator::NotEqual => BinaryOperator::NotEqual,
+        ast::BinaryOperator::LessThan => BinaryOperator::LessThan,
+        ast::BinaryOperator::LessOrEqual => BinaryOperator::LessOrEqual,
+        ast::BinaryOperator::GreaterThan => BinaryOperator::GreaterThan,
+        ast::BinaryOperator::GreaterOrEqual => BinaryOperator::GreaterOrEqual,
+        ast::BinaryOperator::And | ast::BinaryOperator::Or => {
+            unreachable!("logical operators are lowered to jumps")
+        }
+    }
+}
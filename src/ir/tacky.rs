@@ -0,0 +1,155 @@
+//! Three-address intermediate representation in the style of TACKY.
+//! Every instruction operates on constants or named variables, and
+//! intermediate results live in temporaries.
+
+use crate::parser::ast::Const;
+use crate::semantic::symbols::StaticInit;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub top_level: Vec<TopLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopLevel {
+    Function(Function),
+    StaticVariable(StaticVariable),
+    StaticConstant(StaticConstant),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    /// Whether the function is visible to other translation units
+    pub global: bool,
+    pub params: Vec<String>,
+    pub body: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticVariable {
+    pub name: String,
+    pub global: bool,
+    pub init: Vec<StaticInit>,
+}
+
+/// A read-only object, such as a string literal, that is only referred
+/// to by its local label
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticConstant {
+    pub name: String,
+    pub init: StaticInit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    Return(Val),
+    SignExtend {
+        src: Val,
+        dst: Val,
+    },
+    Truncate {
+        src: Val,
+        dst: Val,
+    },
+    ZeroExtend {
+        src: Val,
+        dst: Val,
+    },
+    /// Convert a double to a signed integer, rounding towards zero
+    DoubleToInt {
+        src: Val,
+        dst: Val,
+    },
+    /// Convert a signed integer to the nearest double
+    IntToDouble {
+        src: Val,
+        dst: Val,
+    },
+    Unary {
+        op: UnaryOperator,
+        src: Val,
+        dst: Val,
+    },
+    Binary {
+        op: BinaryOperator,
+        src1: Val,
+        src2: Val,
+        dst: Val,
+    },
+    Copy {
+        src: Val,
+        dst: Val,
+    },
+    /// Store the address of the variable `src` in `dst`
+    GetAddress {
+        src: Val,
+        dst: Val,
+    },
+    /// Copy the value that `src_ptr` points to into `dst`
+    Load {
+        src_ptr: Val,
+        dst: Val,
+    },
+    /// Copy `src` to the location that `dst_ptr` points to
+    Store {
+        src: Val,
+        dst_ptr: Val,
+    },
+    /// Store `ptr + index * scale` in `dst`, where `scale` is the size
+    /// of the type `ptr` points to
+    AddPtr {
+        ptr: Val,
+        index: Val,
+        scale: usize,
+        dst: Val,
+    },
+    /// Copy `src` into the variable `dst`, `offset` bytes from its start
+    CopyToOffset {
+        src: Val,
+        dst: String,
+        offset: usize,
+    },
+    Jump(String),
+    JumpIfZero(Val, String),
+    JumpIfNotZero(Val, String),
+    Label(String),
+    FunCall {
+        name: String,
+        args: Vec<Val>,
+        dst: Val,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Val {
+    Constant(Const),
+    Var(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Complement,
+    Negate,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+}
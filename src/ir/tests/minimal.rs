@@ -0,0 +1,482 @@
+use super::*;
+
+#[test]
+fn test_return_constant() {
+    let program = generate_from("int main(void) { return 2; }");
+    assert_eq!(functions(&program)[0].name, "main");
+    assert_eq!(
+        functions(&program)[0].body,
+        vec![
+            Instruction::Return(Val::Constant(Const::Int(2))),
+            Instruction::Return(Val::Constant(Const::Int(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_nested_unary() {
+    let program = generate_from("int main(void) { return -~!5; }");
+    assert_eq!(
+        functions(&program)[0].body,
+        vec![
+            Instruction::Unary {
+                op: UnaryOperator::Not,
+                src: Val::Constant(Const::Int(5)),
+                dst: var("main.tmp.0"),
+            },
+            Instruction::Unary {
+                op: UnaryOperator::Complement,
+                src: var("main.tmp.0"),
+                dst: var("main.tmp.1"),
+            },
+            Instruction::Unary {
+                op: UnaryOperator::Negate,
+                src: var("main.tmp.1"),
+                dst: var("main.tmp.2"),
+            },
+            Instruction::Return(var("main.tmp.2")),
+            Instruction::Return(Val::Constant(Const::Int(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_logical_and_short_circuits() {
+    let program = generate_from("int main(void) { return 0 && 1; }");
+    assert_eq!(
+        functions(&program)[0].body,
+        vec![
+            Instruction::JumpIfZero(Val::Constant(Const::Int(0)), "and_false.0".to_string()),
+            Instruction::JumpIfZero(Val::Constant(Const::Int(1)), "and_false.0".to_string()),
+            Instruction::Copy {
+                src: Val::Constant(Const::Int(1)),
+                dst: var("main.tmp.2"),
+            },
+            Instruction::Jump("and_end.1".to_string()),
+            Instruction::Label("and_false.0".to_string()),
+            Instruction::Copy {
+                src: Val::Constant(Const::Int(0)),
+                dst: var("main.tmp.2"),
+            },
+            Instruction::Label("and_end.1".to_string()),
+            Instruction::Return(var("main.tmp.2")),
+            Instruction::Return(Val::Constant(Const::Int(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_function_call() {
+    let program = generate_from("int f(int a, int b); int main(void) { return f(1, 2); }");
+    assert_eq!(functions(&program).len(), 1);
+    assert_eq!(
+        functions(&program)[0].body,
+        vec![
+            Instruction::FunCall {
+                name: "f".to_string(),
+                args: vec![Val::Constant(Const::Int(1)), Val::Constant(Const::Int(2))],
+                dst: var("main.tmp.0"),
+            },
+            Instruction::Return(var("main.tmp.0")),
+            Instruction::Return(Val::Constant(Const::Int(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_labels_are_unique_across_functions() {
+    let program =
+        generate_from("int f(int a) { return a ? 1 : 2; } int main(void) { return 1 ? 3 : 4; }");
+    let labels: Vec<&Instruction> = program
+        .top_level
+        .iter()
+        .filter_map(|item| match item {
+            TopLevel::Function(function) => Some(function),
+            TopLevel::StaticVariable(_) | TopLevel::StaticConstant(_) => None,
+        })
+        .flat_map(|function| &function.body)
+        .filter(|i| matches!(i, Instruction::Label(_)))
+        .collect();
+    assert_eq!(labels.len(), 4);
+    for (i, label) in labels.iter().enumerate() {
+        assert!(!labels[i + 1..].contains(label));
+    }
+    assert_eq!(functions(&program)[0].params, vec!["a.0"]);
+}
+
+#[test]
+fn test_static_variables() {
+    let program = generate_from(
+        "static int a = 3; int b; extern int c; int main(void) { static int d; return a + b + c + d; }",
+    );
+    let statics: Vec<&StaticVariable> = program
+        .top_level
+        .iter()
+        .filter_map(|item| match item {
+            TopLevel::StaticVariable(var) => Some(var),
+            TopLevel::Function(_) | TopLevel::StaticConstant(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        statics,
+        vec![
+            &StaticVariable {
+                name: "a".to_string(),
+                global: false,
+                init: vec![StaticInit::Int(3)],
+            },
+            &StaticVariable {
+                name: "b".to_string(),
+                global: true,
+                init: vec![StaticInit::Zero(4)],
+            },
+            &StaticVariable {
+                name: "d.0".to_string(),
+                global: false,
+                init: vec![StaticInit::Zero(4)],
+            },
+        ]
+    );
+    // Static locals are initialized before the program starts
+    assert!(!functions(&program)[0]
+        .body
+        .iter()
+        .any(|i| matches!(i, Instruction::Copy { .. })));
+}
+
+#[test]
+fn test_conversions() {
+    let program = generate_from("long main(void) { int i = 1; long l = i; return (int) l; }");
+    assert_eq!(
+        functions(&program)[0].body,
+        vec![
+            Instruction::Copy {
+                src: Val::Constant(Const::Int(1)),
+                dst: var("i.0"),
+            },
+            Instruction::SignExtend {
+                src: var("i.0"),
+                dst: var("main.tmp.0"),
+            },
+            Instruction::Copy {
+                src: var("main.tmp.0"),
+                dst: var("l.1"),
+            },
+            Instruction::Truncate {
+                src: var("l.1"),
+                dst: var("main.tmp.1"),
+            },
+            Instruction::SignExtend {
+                src: var("main.tmp.1"),
+                dst: var("main.tmp.2"),
+            },
+            Instruction::Return(var("main.tmp.2")),
+            Instruction::Return(Val::Constant(Const::Long(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_temporaries_are_typed() {
+    let mut program = Parser::new("int main(void) { long l = 2; return l * 3 > 4; }")
+        .parse_program()
+        .unwrap();
+    let mut symbols = semantic::analyze(&mut program).unwrap();
+    generate(&program, &mut symbols);
+    // The product is long, and the comparison is int
+    let temporaries: Vec<&ast::Type> = symbols
+        .iter()
+        .filter(|(name, _)| name.starts_with("main.tmp."))
+        .map(|(_, symbol)| &symbol.ty)
+        .collect();
+    assert_eq!(temporaries, vec![&ast::Type::Long, &ast::Type::Int]);
+}
+
+#[test]
+fn test_unsigned_conversions() {
+    let program = generate_from(
+        "int main(void) { unsigned u = 1; unsigned long l = u; int i = u; return l + i; }",
+    );
+    let body = &functions(&program)[0].body;
+    assert_eq!(
+        body[1],
+        Instruction::ZeroExtend {
+            src: var("u.0"),
+            dst: var("main.tmp.0"),
+        }
+    );
+    // Conversions between types of the same size only copy the value
+    assert_eq!(
+        body[3],
+        Instruction::Copy {
+            src: var("u.0"),
+            dst: var("main.tmp.1"),
+        }
+    );
+    assert!(body.contains(&Instruction::SignExtend {
+        src: var("i.2"),
+        dst: var("main.tmp.2"),
+    }));
+}
+
+#[test]
+fn test_double_conversions() {
+    let program = generate_from(
+        "int main(void) { double d = 2.5; long l = d; d = l; unsigned long u = d; return !d; }",
+    );
+    let body = &functions(&program)[0].body;
+    assert_eq!(
+        body[1],
+        Instruction::DoubleToInt {
+            src: var("d.0"),
+            dst: var("main.tmp.0"),
+        }
+    );
+    assert_eq!(
+        body[3],
+        Instruction::IntToDouble {
+            src: var("l.1"),
+            dst: var("main.tmp.1"),
+        }
+    );
+    // Conversion to unsigned long checks whether the value is beyond
+    // the signed range
+    assert!(body.contains(&Instruction::Binary {
+        op: BinaryOperator::GreaterOrEqual,
+        src1: var("d.0"),
+        src2: Val::Constant(Const::Double(9223372036854775808.0)),
+        dst: var("main.tmp.5"),
+    }));
+    // Logical not compares with zero, so that NaN is true
+    assert!(body.contains(&Instruction::Binary {
+        op: BinaryOperator::Equal,
+        src1: var("d.0"),
+        src2: Val::Constant(Const::Double(0.0)),
+        dst: var("main.tmp.7"),
+    }));
+}
+
+#[test]
+fn test_double_conditions_compare_with_zero() {
+    let program = generate_from("int main(void) { double d = 0.5; if (d) return 1; return 0; }");
+    let body = &functions(&program)[0].body;
+    assert_eq!(
+        &body[1..3],
+        &[
+            Instruction::Binary {
+                op: BinaryOperator::NotEqual,
+                src1: var("d.0"),
+                src2: Val::Constant(Const::Double(0.0)),
+                dst: var("main.tmp.1"),
+            },
+            Instruction::JumpIfZero(var("main.tmp.1"), "if_end.0".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_pointers() {
+    let program =
+        generate_from("int main(void) { int x = 1; int *p = &x; *p = *p + 2; return x; }");
+    let body = &functions(&program)[0].body;
+    assert_eq!(
+        &body[1..],
+        &[
+            Instruction::GetAddress {
+                src: var("x.0"),
+                dst: var("main.tmp.0"),
+            },
+            Instruction::Copy {
+                src: var("main.tmp.0"),
+                dst: var("p.1"),
+            },
+            Instruction::Load {
+                src_ptr: var("p.1"),
+                dst: var("main.tmp.1"),
+            },
+            Instruction::Binary {
+                op: BinaryOperator::Add,
+                src1: var("main.tmp.1"),
+                src2: Val::Constant(Const::Int(2)),
+                dst: var("main.tmp.2"),
+            },
+            Instruction::Store {
+                src: var("main.tmp.2"),
+                dst_ptr: var("p.1"),
+            },
+            Instruction::Return(var("x.0")),
+            Instruction::Return(Val::Constant(Const::Int(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_arrays() {
+    let program = generate_from("int main(void) { int a[3] = {1, 2}; return a[1] + (&a[2] - a); }");
+    let body = &functions(&program)[0].body;
+    let long = |value| Val::Constant(Const::Long(value));
+    assert_eq!(
+        &body[..],
+        &[
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Int(1)),
+                dst: "a.0".to_string(),
+                offset: 0,
+            },
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Int(2)),
+                dst: "a.0".to_string(),
+                offset: 4,
+            },
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Int(0)),
+                dst: "a.0".to_string(),
+                offset: 8,
+            },
+            Instruction::GetAddress {
+                src: var("a.0"),
+                dst: var("main.tmp.0"),
+            },
+            Instruction::AddPtr {
+                ptr: var("main.tmp.0"),
+                index: long(1),
+                scale: 4,
+                dst: var("main.tmp.1"),
+            },
+            Instruction::Load {
+                src_ptr: var("main.tmp.1"),
+                dst: var("main.tmp.2"),
+            },
+            Instruction::SignExtend {
+                src: var("main.tmp.2"),
+                dst: var("main.tmp.3"),
+            },
+            Instruction::GetAddress {
+                src: var("a.0"),
+                dst: var("main.tmp.4"),
+            },
+            Instruction::AddPtr {
+                ptr: var("main.tmp.4"),
+                index: long(2),
+                scale: 4,
+                dst: var("main.tmp.5"),
+            },
+            Instruction::GetAddress {
+                src: var("a.0"),
+                dst: var("main.tmp.6"),
+            },
+            // The difference in bytes is divided by the element size
+            Instruction::Binary {
+                op: BinaryOperator::Subtract,
+                src1: var("main.tmp.5"),
+                src2: var("main.tmp.6"),
+                dst: var("main.tmp.7"),
+            },
+            Instruction::Binary {
+                op: BinaryOperator::Divide,
+                src1: var("main.tmp.7"),
+                src2: long(4),
+                dst: var("main.tmp.8"),
+            },
+            Instruction::Binary {
+                op: BinaryOperator::Add,
+                src1: var("main.tmp.3"),
+                src2: var("main.tmp.8"),
+                dst: var("main.tmp.9"),
+            },
+            Instruction::Truncate {
+                src: var("main.tmp.9"),
+                dst: var("main.tmp.10"),
+            },
+            Instruction::Return(var("main.tmp.10")),
+            Instruction::Return(Val::Constant(Const::Int(0))),
+        ]
+    );
+}
+
+#[test]
+fn test_pointer_subtraction_negates_index() {
+    let program = generate_from("long *f(long *p, int n) { return p - n - 1; }");
+    let body = &functions(&program)[0].body;
+    assert_eq!(
+        &body[..4],
+        &[
+            Instruction::SignExtend {
+                src: var("n.1"),
+                dst: var("f.tmp.0"),
+            },
+            Instruction::Unary {
+                op: UnaryOperator::Negate,
+                src: var("f.tmp.0"),
+                dst: var("f.tmp.1"),
+            },
+            Instruction::AddPtr {
+                ptr: var("p.0"),
+                index: var("f.tmp.1"),
+                scale: 8,
+                dst: var("f.tmp.2"),
+            },
+            Instruction::AddPtr {
+                ptr: var("f.tmp.2"),
+                index: Val::Constant(Const::Long(-1)),
+                scale: 8,
+                dst: var("f.tmp.3"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_string_initializer() {
+    let program = generate_from(r#"int main(void) { char s[11] = "abcdefghi"; return s[0]; }"#);
+    let body = &functions(&program)[0].body;
+    // Eight bytes are copied at a time, then the rest one by one
+    assert_eq!(
+        &body[..4],
+        &[
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Long(i64::from_le_bytes(*b"abcdefgh"))),
+                dst: "s.0".to_string(),
+                offset: 0,
+            },
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Char(b'i' as i8)),
+                dst: "s.0".to_string(),
+                offset: 8,
+            },
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Char(0)),
+                dst: "s.0".to_string(),
+                offset: 9,
+            },
+            Instruction::CopyToOffset {
+                src: Val::Constant(Const::Char(0)),
+                dst: "s.0".to_string(),
+                offset: 10,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_string_constants() {
+    let program = generate_from(r#"int main(void) { char *p = "hi"; return 0; }"#);
+    let constants: Vec<_> = program
+        .top_level
+        .iter()
+        .filter_map(|item| match item {
+            TopLevel::StaticConstant(constant) => Some(constant),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        constants,
+        vec![&StaticConstant {
+            name: ".Lstring.0".to_string(),
+            init: StaticInit::String {
+                bytes: b"hi".to_vec(),
+                null_terminated: true,
+            },
+        }]
+    );
+}
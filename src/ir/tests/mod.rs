@@ -0,0 +1,31 @@
+use super::*;
+use crate::parser::ast::Const;
+use crate::parser::Parser;
+use crate::semantic;
+use crate::semantic::symbols::StaticInit;
+
+mod minimal;
+
+#[cfg(test)]
+fn generate_from(input: &str) -> Program {
+    let mut program = Parser::new(input).parse_program().expect("parse failed");
+    let mut symbols = semantic::analyze(&mut program).expect("semantic analysis failed");
+    generate(&program, &mut symbols)
+}
+
+#[cfg(test)]
+fn functions(program: &Program) -> Vec<&Function> {
+    program
+        .top_level
+        .iter()
+        .filter_map(|item| match item {
+            TopLevel::Function(function) => Some(function),
+            TopLevel::StaticVariable(_) | TopLevel::StaticConstant(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn var(name: &str) -> Val {
+    Val::Var(name.to_string())
+}
@@ -0,0 +1,346 @@
+//! Abstract syntax tree for the subset of C understood by the parser
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub declarations: Vec<Declaration>,
+}
+
+/// A function declaration, which is also a definition when it has a body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionDeclaration {
+    pub name: String,
+    pub params: Vec<String>,
+    /// Always a function type
+    pub fun_type: Type,
+    pub body: Option<Block>,
+    pub storage_class: Option<StorageClass>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Char,
+    SChar,
+    UChar,
+    Int,
+    Long,
+    UInt,
+    ULong,
+    Double,
+    Pointer(Box<Type>),
+    /// An array of a fixed number of elements, or of zero when an
+    /// extern declaration leaves the size out
+    Array(Box<Type>, usize),
+    Function {
+        params: Vec<Type>,
+        ret: Box<Type>,
+    },
+}
+
+impl Type {
+    /// The size of a value of this type in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            Type::Char | Type::SChar | Type::UChar => 1,
+            Type::Int | Type::UInt => 4,
+            Type::Long | Type::ULong | Type::Double | Type::Pointer(_) => 8,
+            Type::Array(element, count) => element.size() * count,
+            Type::Function { .. } => unreachable!("functions have no size"),
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        // Plain char is signed, as it is in the System V ABI
+        matches!(self, Type::Char | Type::SChar | Type::Int | Type::Long)
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        matches!(self, Type::Pointer(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Type::Array(..))
+    }
+
+    pub fn is_arithmetic(&self) -> bool {
+        matches!(
+            self,
+            Type::Char
+                | Type::SChar
+                | Type::UChar
+                | Type::Int
+                | Type::Long
+                | Type::UInt
+                | Type::ULong
+                | Type::Double
+        )
+    }
+
+    pub fn is_character(&self) -> bool {
+        matches!(self, Type::Char | Type::SChar | Type::UChar)
+    }
+}
+
+/// Types are written the way C spells them in diagnostics
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Char => write!(f, "char"),
+            Type::SChar => write!(f, "signed char"),
+            Type::UChar => write!(f, "unsigned char"),
+            Type::Int => write!(f, "int"),
+            Type::Long => write!(f, "long"),
+            Type::UInt => write!(f, "unsigned int"),
+            Type::ULong => write!(f, "unsigned long"),
+            Type::Double => write!(f, "double"),
+            Type::Pointer(referenced) => match **referenced {
+                Type::Pointer(_) => write!(f, "{}*", referenced),
+                _ => write!(f, "{} *", referenced),
+            },
+            Type::Array(..) => {
+                // The dimensions follow the innermost element type in
+                // the order they were declared: int[2][3]
+                let mut element = self;
+                let mut dimensions = String::new();
+                while let Type::Array(inner, count) = element {
+                    match count {
+                        0 => dimensions.push_str("[]"),
+                        _ => dimensions.push_str(&format!("[{}]", count)),
+                    }
+                    element = inner;
+                }
+                write!(f, "{}{}", element, dimensions)
+            }
+            Type::Function { params, ret } => {
+                write!(f, "{} (", ret)?;
+                for (i, param) in params.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", param)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageClass {
+    Static,
+    Extern,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block(pub Vec<BlockItem>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockItem {
+    Statement(Statement),
+    Declaration(Declaration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Declaration {
+    Variable(VariableDeclaration),
+    Function(FunctionDeclaration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDeclaration {
+    pub name: String,
+    pub var_type: Type,
+    pub init: Option<Initializer>,
+    pub storage_class: Option<StorageClass>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Initializer {
+    Single(Expression),
+    /// A brace-enclosed list of initializers for the elements of an
+    /// array
+    Compound(Vec<Initializer>, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Return(Expression),
+    Expression(Expression),
+    If(Expression, Box<Statement>, Option<Box<Statement>>),
+    Compound(Block),
+    /// The label of a loop, break or continue statement ties it to its
+    /// enclosing loop. It is left empty by the parser and filled in by
+    /// semantic analysis.
+    Break {
+        label: String,
+        line: usize,
+    },
+    Continue {
+        label: String,
+        line: usize,
+    },
+    While {
+        condition: Expression,
+        body: Box<Statement>,
+        label: String,
+    },
+    DoWhile {
+        body: Box<Statement>,
+        condition: Expression,
+        label: String,
+    },
+    For {
+        init: Box<ForInit>,
+        condition: Option<Expression>,
+        post: Option<Expression>,
+        body: Box<Statement>,
+        label: String,
+    },
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForInit {
+    Declaration(VariableDeclaration),
+    Expression(Option<Expression>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    /// Filled in by the type checker
+    pub ty: Option<Type>,
+    pub line: usize,
+}
+
+impl Expression {
+    pub fn new(kind: ExpressionKind, line: usize) -> Self {
+        Self {
+            kind,
+            ty: None,
+            line,
+        }
+    }
+
+    /// The type of the expression, once type checking has run
+    pub fn ty(&self) -> &Type {
+        self.ty
+            .as_ref()
+            .expect("expressions are typed before they are used")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Const {
+    Char(i8),
+    UChar(u8),
+    Int(i32),
+    Long(i64),
+    UInt(u32),
+    ULong(u64),
+    Double(f64),
+}
+
+impl Const {
+    pub fn ty(&self) -> Type {
+        match self {
+            Const::Char(_) => Type::Char,
+            Const::UChar(_) => Type::UChar,
+            Const::Int(_) => Type::Int,
+            Const::Long(_) => Type::Long,
+            Const::UInt(_) => Type::UInt,
+            Const::ULong(_) => Type::ULong,
+            Const::Double(_) => Type::Double,
+        }
+    }
+
+    /// Convert the constant to another type at compile time. Integers
+    /// wrap around as the conversion would at run time, and doubles are
+    /// truncated towards zero.
+    pub fn convert_to(self, ty: &Type) -> Const {
+        if *ty == Type::Double {
+            return Const::Double(match self {
+                Const::Char(value) => value as f64,
+                Const::UChar(value) => value as f64,
+                Const::Int(value) => value as f64,
+                Const::Long(value) => value as f64,
+                Const::UInt(value) => value as f64,
+                Const::ULong(value) => value as f64,
+                Const::Double(value) => value,
+            });
+        }
+        let value = match self {
+            Const::Char(value) => value as i64,
+            Const::UChar(value) => value as i64,
+            Const::Int(value) => value as i64,
+            Const::Long(value) => value,
+            Const::UInt(value) => value as i64,
+            Const::ULong(value) => value as i64,
+            Const::Double(value) if *ty == Type::ULong => value as u64 as i64,
+            Const::Double(value) => value as i64,
+        };
+        match ty {
+            Type::Char | Type::SChar => Const::Char(value as i8),
+            Type::UChar => Const::UChar(value as u8),
+            Type::Int => Const::Int(value as i32),
+            Type::Long => Const::Long(value),
+            Type::UInt => Const::UInt(value as u32),
+            // Only null pointer constants are converted to pointers
+            Type::ULong | Type::Pointer(_) => Const::ULong(value as u64),
+            Type::Double => unreachable!("conversions to double are handled above"),
+            Type::Array(..) | Type::Function { .. } => {
+                unreachable!("constants cannot have array or function type")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind {
+    Constant(Const),
+    /// A string literal, as the bytes it stands for without the
+    /// terminating null
+    String(Vec<u8>),
+    Var(String),
+    Cast(Type, Box<Expression>),
+    Unary(UnaryOperator, Box<Expression>),
+    Dereference(Box<Expression>),
+    AddressOf(Box<Expression>),
+    Subscript(Box<Expression>, Box<Expression>),
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>),
+    Assignment(Box<Expression>, Box<Expression>),
+    Conditional(Box<Expression>, Box<Expression>, Box<Expression>),
+    FunctionCall(String, Vec<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    Negate,
+    Complement,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
+    And,
+    Or,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+}
@@ -0,0 +1,947 @@
+pub mod ast;
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+use crate::parser::ast::*;
+use crate::scanner::token::*;
+use crate::scanner::Scanner;
+use crate::semantic::constant::{constant_value, is_zero};
+
+#[derive(Debug, Clone)]
+pub enum ParseError {
+    /// A token of a specific type was required
+    Expected(TokenType, Token),
+    /// A construct such as an expression or a statement was required
+    ExpectedConstruct(&'static str, Token),
+    /// An integer constant that is malformed or does not fit in its type
+    InvalidConstant(Token),
+    /// A combination of declaration specifiers that is not allowed
+    InvalidSpecifiers(&'static str, Token),
+    /// A declarator that derives a type the compiler does not support
+    InvalidDeclarator(&'static str, Token),
+}
+
+impl ParseError {
+    pub fn line(&self) -> usize {
+        match self {
+            ParseError::Expected(_, tok)
+            | ParseError::ExpectedConstruct(_, tok)
+            | ParseError::InvalidConstant(tok)
+            | ParseError::InvalidSpecifiers(_, tok)
+            | ParseError::InvalidDeclarator(_, tok) => tok.line,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // The scanner already describes what is wrong with the token
+            ParseError::Expected(_, found) | ParseError::ExpectedConstruct(_, found)
+                if found.ttype == TokenType::Error =>
+            {
+                write!(f, "{}", found.lexeme)
+            }
+            ParseError::Expected(expected, found) => {
+                write!(f, "expected '{}', found {}", expected, describe(found))
+            }
+            ParseError::ExpectedConstruct(expected, found) => {
+                write!(f, "expected {}, found {}", expected, describe(found))
+            }
+            ParseError::InvalidConstant(tok)
+                if tok
+                    .lexeme
+                    .trim_end_matches(['l', 'L', 'u', 'U'])
+                    .chars()
+                    .all(|c| c.is_ascii_digit()) =>
+            {
+                write!(f, "integer constant '{}' is too large", tok.lexeme)
+            }
+            ParseError::InvalidConstant(tok) => {
+                write!(f, "invalid integer constant '{}'", tok.lexeme)
+            }
+            ParseError::InvalidSpecifiers(msg, _) | ParseError::InvalidDeclarator(msg, _) => {
+                write!(f, "{}", msg)
+            }
+        }
+    }
+}
+
+fn describe(tok: &Token) -> String {
+    match tok.ttype {
+        TokenType::Eof => "end of file".to_string(),
+        TokenType::Identifier
+        | TokenType::Integer
+        | TokenType::LongInteger
+        | TokenType::UnsignedInteger
+        | TokenType::UnsignedLongInteger
+        | TokenType::FloatLiteral => {
+            format!("{} '{}'", tok.ttype, tok.lexeme)
+        }
+        TokenType::CharLiteral => format!("{} '{}'", tok.ttype, tok.lexeme.escape_default()),
+        TokenType::StringLiteral => format!("{} \"{}\"", tok.ttype, tok.lexeme.escape_default()),
+        _ => format!("'{}'", tok.ttype),
+    }
+}
+
+/// The value of an integer constant token. A constant has the first
+/// type that can represent it, starting from the type given by its
+/// suffix: int or long without a 'u', unsigned int or unsigned long
+/// with one. Floating constants are doubles.
+fn constant(tok: &Token) -> Result<Const, ParseError> {
+    let invalid = || ParseError::InvalidConstant(tok.clone());
+    if tok.ttype == TokenType::FloatLiteral {
+        return tok.lexeme.parse().map(Const::Double).map_err(|_| invalid());
+    }
+    // A character constant has type int, and the value of its byte as
+    // a (signed) char
+    if tok.ttype == TokenType::CharLiteral {
+        let byte = tok.lexeme.chars().next().ok_or_else(invalid)? as u8;
+        return Ok(Const::Int(byte as i8 as i32));
+    }
+    let digits = tok.lexeme.trim_end_matches(['l', 'L', 'u', 'U']);
+    let value = digits.parse::<u64>().map_err(|_| invalid())?;
+    match tok.ttype {
+        TokenType::Integer | TokenType::LongInteger => {
+            let value = i64::try_from(value).map_err(|_| invalid())?;
+            match i32::try_from(value) {
+                Ok(value) if tok.ttype == TokenType::Integer => Ok(Const::Int(value)),
+                _ => Ok(Const::Long(value)),
+            }
+        }
+        _ => match u32::try_from(value) {
+            Ok(value) if tok.ttype == TokenType::UnsignedInteger => Ok(Const::UInt(value)),
+            _ => Ok(Const::ULong(value)),
+        },
+    }
+}
+
+/// A declarator as written, before it is applied to the base type from
+/// the declaration specifiers. Declarators read inside out: in
+/// `int *f(void)` the pointer applies to the result of the function.
+enum Declarator {
+    Ident(Token),
+    Pointer(Box<Declarator>),
+    /// An array, with no size if it was left out
+    Array(Box<Declarator>, Option<usize>),
+    /// A parameter list, with the base type and declarator of each
+    /// parameter
+    Function(Vec<(Type, Declarator)>, Box<Declarator>),
+}
+
+impl Declarator {
+    /// The identifier being declared
+    fn name(&self) -> &Token {
+        match self {
+            Declarator::Ident(name) => name,
+            Declarator::Pointer(inner)
+            | Declarator::Array(inner, _)
+            | Declarator::Function(_, inner) => inner.name(),
+        }
+    }
+}
+
+/// Apply a declarator to a base type, returning the declared name, its
+/// type and, for a function, the names of its parameters
+fn declare(declarator: Declarator, base: Type) -> Result<(Token, Type, Vec<String>), ParseError> {
+    match declarator {
+        Declarator::Ident(name) => Ok((name, base, Vec::new())),
+        Declarator::Pointer(inner) => declare(*inner, Type::Pointer(Box::new(base))),
+        Declarator::Array(inner, Some(count)) => {
+            let ty = array_of(base, count, inner.name())?;
+            declare(*inner, ty)
+        }
+        // Only the array being declared can leave out its size. It has a
+        // size of zero until the initializer of a variable supplies one,
+        // and a parameter is adjusted to a pointer anyway.
+        Declarator::Array(inner, None) => match *inner {
+            Declarator::Ident(name) => Ok((name, Type::Array(Box::new(base), 0), Vec::new())),
+            inner => Err(ParseError::InvalidDeclarator(
+                "array size missing",
+                inner.name().clone(),
+            )),
+        },
+        Declarator::Function(params, inner) => {
+            let Declarator::Ident(name) = *inner else {
+                return Err(ParseError::InvalidDeclarator(
+                    "function pointers are not supported",
+                    inner.name().clone(),
+                ));
+            };
+            if base.is_array() {
+                return Err(ParseError::InvalidDeclarator(
+                    "functions cannot return arrays",
+                    name,
+                ));
+            }
+            let mut param_types = Vec::new();
+            let mut param_names = Vec::new();
+            for (param_base, param) in params {
+                let (param_name, ty, _) = declare(param, param_base)?;
+                let ty = match ty {
+                    Type::Function { .. } => {
+                        return Err(ParseError::InvalidDeclarator(
+                            "function pointers are not supported",
+                            param_name,
+                        ))
+                    }
+                    // An array parameter is adjusted to a pointer to its
+                    // first element
+                    Type::Array(element, _) => Type::Pointer(element),
+                    ty => ty,
+                };
+                param_types.push(ty);
+                param_names.push(param_name.lexeme);
+            }
+            let fun_type = Type::Function {
+                params: param_types,
+                ret: Box::new(base),
+            };
+            Ok((name, fun_type, param_names))
+        }
+    }
+}
+
+/// An array of `count` elements, provided that its size in bytes can
+/// be represented. `tok` is where the error is reported.
+fn array_of(element: Type, count: usize, tok: &Token) -> Result<Type, ParseError> {
+    match element.size().checked_mul(count) {
+        Some(size) if size <= i64::MAX as usize => Ok(Type::Array(Box::new(element), count)),
+        _ => Err(ParseError::InvalidDeclarator(
+            "array is too large",
+            tok.clone(),
+        )),
+    }
+}
+
+/// A declarator without a name, as used in the type name of a cast.
+/// Each array dimension keeps its opening bracket for error reporting.
+enum AbstractDeclarator {
+    Pointer(Box<AbstractDeclarator>),
+    Array(Box<AbstractDeclarator>, usize, Token),
+    Base,
+}
+
+fn abstract_type(declarator: AbstractDeclarator, base: Type) -> Result<Type, ParseError> {
+    match declarator {
+        AbstractDeclarator::Pointer(inner) => abstract_type(*inner, Type::Pointer(Box::new(base))),
+        AbstractDeclarator::Array(inner, count, tok) => {
+            abstract_type(*inner, array_of(base, count, &tok)?)
+        }
+        AbstractDeclarator::Base => Ok(base),
+    }
+}
+
+const ASSIGNMENT_PRECEDENCE: u8 = 1;
+const CONDITIONAL_PRECEDENCE: u8 = 3;
+
+/// The binary operator for a token along with its precedence. Higher
+/// values bind more tightly.
+fn binary_operator(ttype: TokenType) -> Option<(BinaryOperator, u8)> {
+    match ttype {
+        TokenType::Star => Some((BinaryOperator::Multiply, 50)),
+        TokenType::Slash => Some((BinaryOperator::Divide, 50)),
+        TokenType::Percent => Some((BinaryOperator::Remainder, 50)),
+        TokenType::Plus => Some((BinaryOperator::Add, 45)),
+        TokenType::Minus => Some((BinaryOperator::Subtract, 45)),
+        TokenType::LessLess => Some((BinaryOperator::ShiftLeft, 40)),
+        TokenType::GreaterGreater => Some((BinaryOperator::ShiftRight, 40)),
+        TokenType::Less => Some((BinaryOperator::LessThan, 35)),
+        TokenType::LessEqual => Some((BinaryOperator::LessOrEqual, 35)),
+        TokenType::Greater => Some((BinaryOperator::GreaterThan, 35)),
+        TokenType::GreaterEqual => Some((BinaryOperator::GreaterOrEqual, 35)),
+        TokenType::EqualEqual => Some((BinaryOperator::Equal, 30)),
+        TokenType::BangEqual => Some((BinaryOperator::NotEqual, 30)),
+        TokenType::Ampersand => Some((BinaryOperator::BitwiseAnd, 25)),
+        TokenType::Caret => Some((BinaryOperator::BitwiseXor, 20)),
+        TokenType::Pipe => Some((BinaryOperator::BitwiseOr, 15)),
+        TokenType::AmpersandAmpersand => Some((BinaryOperator::And, 10)),
+        TokenType::PipePipe => Some((BinaryOperator::Or, 5)),
+        _ => None,
+    }
+}
+
+/// Recursive descent parser that pulls tokens from the scanner on demand
+pub struct Parser {
+    scanner: Scanner,
+    current: Token,
+}
+
+impl Parser {
+    pub fn new(source: &str) -> Self {
+        let mut scanner = Scanner::new(source);
+        let current = scanner.scan_token();
+        Self { scanner, current }
+    }
+
+    pub fn parse_program(&mut self) -> Result<Program, ParseError> {
+        let mut declarations = Vec::new();
+        while !self.check(TokenType::Eof) {
+            declarations.push(self.declaration()?);
+        }
+        Ok(Program { declarations })
+    }
+
+    /// Parse a declaration. A declarator that ends in a parameter list
+    /// makes it a function declaration.
+    fn declaration(&mut self) -> Result<Declaration, ParseError> {
+        let (base, storage_class) = self.specifiers()?;
+        let (name, ty, params) = declare(self.declarator()?, base)?;
+        if let Type::Function { .. } = ty {
+            Ok(Declaration::Function(self.function_declaration(
+                name,
+                ty,
+                params,
+                storage_class,
+            )?))
+        } else {
+            Ok(Declaration::Variable(self.variable_declaration(
+                name,
+                ty,
+                storage_class,
+            )?))
+        }
+    }
+
+    fn at_type_specifier(&self) -> bool {
+        matches!(
+            self.current.ttype,
+            TokenType::Char
+                | TokenType::Int
+                | TokenType::Long
+                | TokenType::Signed
+                | TokenType::Unsigned
+                | TokenType::Double
+        )
+    }
+
+    fn at_specifier(&self) -> bool {
+        self.at_type_specifier()
+            || matches!(self.current.ttype, TokenType::Static | TokenType::Extern)
+    }
+
+    /// Parse the declaration specifiers, which may come in any order, and
+    /// return the type and storage class
+    fn specifiers(&mut self) -> Result<(Type, Option<StorageClass>), ParseError> {
+        let mut type_specifiers = Vec::new();
+        let mut storage_class = None;
+        while self.at_specifier() {
+            let tok = self.advance();
+            match tok.ttype {
+                TokenType::Static | TokenType::Extern if storage_class.is_some() => {
+                    return Err(ParseError::InvalidSpecifiers(
+                        "multiple storage classes in declaration specifiers",
+                        tok,
+                    ))
+                }
+                TokenType::Static => storage_class = Some(StorageClass::Static),
+                TokenType::Extern => storage_class = Some(StorageClass::Extern),
+                _ => type_specifiers.push(tok),
+            }
+        }
+        Ok((self.type_from_specifiers(type_specifiers)?, storage_class))
+    }
+
+    /// Parse the type specifiers of a parameter, which cannot have a
+    /// storage class
+    fn type_specifiers(&mut self) -> Result<Type, ParseError> {
+        let mut type_specifiers = Vec::new();
+        while self.at_type_specifier() {
+            type_specifiers.push(self.advance());
+        }
+        self.type_from_specifiers(type_specifiers)
+    }
+
+    /// Parse the type name of a cast: type specifiers followed by an
+    /// optional abstract declarator
+    fn type_name(&mut self) -> Result<Type, ParseError> {
+        let base = self.type_specifiers()?;
+        abstract_type(self.abstract_declarator()?, base)
+    }
+
+    /// Parse a declarator: any number of '*' followed by an identifier,
+    /// which may be parenthesized, and either a parameter list or any
+    /// number of array dimensions
+    fn declarator(&mut self) -> Result<Declarator, ParseError> {
+        if self.check(TokenType::Star) {
+            self.advance();
+            return Ok(Declarator::Pointer(Box::new(self.declarator()?)));
+        }
+        let inner = if self.check(TokenType::LeftParen) {
+            self.advance();
+            let inner = self.declarator()?;
+            self.consume(TokenType::RightParen)?;
+            inner
+        } else {
+            Declarator::Ident(self.consume(TokenType::Identifier)?)
+        };
+        if self.check(TokenType::LeftParen) {
+            let params = self.parameter_list()?;
+            return Ok(Declarator::Function(params, Box::new(inner)));
+        }
+        let mut declarator = inner;
+        while self.check(TokenType::LeftBracket) {
+            declarator = Declarator::Array(Box::new(declarator), self.array_dimension()?);
+        }
+        Ok(declarator)
+    }
+
+    /// Parse an array dimension in brackets, which must be a positive
+    /// integer constant expression if it is not left out
+    fn array_dimension(&mut self) -> Result<Option<usize>, ParseError> {
+        self.consume(TokenType::LeftBracket)?;
+        if self.check(TokenType::RightBracket) {
+            self.advance();
+            return Ok(None);
+        }
+        let tok = self.current.clone();
+        let size = self.binary_expression(CONDITIONAL_PRECEDENCE)?;
+        let value = match constant_value(&size) {
+            Some(value) if value.ty() != Type::Double => value,
+            _ => {
+                return Err(ParseError::InvalidDeclarator(
+                    "array size is not an integer constant",
+                    tok,
+                ))
+            }
+        };
+        let Const::Long(count) = value.convert_to(&Type::Long) else {
+            unreachable!("converted to long")
+        };
+        if is_zero(value) || (count < 0 && value.ty().is_signed()) {
+            return Err(ParseError::InvalidDeclarator(
+                "array size must be greater than zero",
+                tok,
+            ));
+        }
+        // An unsigned size that does not fit in a long is too large for
+        // any element type
+        if count < 0 {
+            return Err(ParseError::InvalidDeclarator("array is too large", tok));
+        }
+        self.consume(TokenType::RightBracket)?;
+        Ok(Some(count as usize))
+    }
+
+    /// Parse an optional abstract declarator. A parenthesized one
+    /// cannot be empty.
+    fn abstract_declarator(&mut self) -> Result<AbstractDeclarator, ParseError> {
+        let mut declarator = match self.current.ttype {
+            TokenType::Star => {
+                self.advance();
+                return Ok(AbstractDeclarator::Pointer(Box::new(
+                    self.abstract_declarator()?,
+                )));
+            }
+            TokenType::LeftParen => {
+                self.advance();
+                self.consume(TokenType::Star)?;
+                let inner = AbstractDeclarator::Pointer(Box::new(self.abstract_declarator()?));
+                self.consume(TokenType::RightParen)?;
+                inner
+            }
+            _ => AbstractDeclarator::Base,
+        };
+        while self.check(TokenType::LeftBracket) {
+            let tok = self.current.clone();
+            let Some(count) = self.array_dimension()? else {
+                return Err(ParseError::InvalidDeclarator("array size missing", tok));
+            };
+            declarator = AbstractDeclarator::Array(Box::new(declarator), count, tok);
+        }
+        Ok(declarator)
+    }
+
+    /// Work out the type named by a list of type specifiers, which may
+    /// come in any order
+    fn type_from_specifiers(&self, specifiers: Vec<Token>) -> Result<Type, ParseError> {
+        if specifiers.is_empty() {
+            return Err(ParseError::Expected(TokenType::Int, self.current.clone()));
+        }
+        // double cannot be combined with any other type specifier
+        if let Some(tok) = specifiers.iter().find(|tok| tok.ttype == TokenType::Double) {
+            if specifiers.len() > 1 {
+                return Err(ParseError::InvalidSpecifiers(
+                    "two or more data types in declaration specifiers",
+                    tok.clone(),
+                ));
+            }
+            return Ok(Type::Double);
+        }
+        // char can only be combined with signed or unsigned
+        let has_char = specifiers.iter().any(|tok| tok.ttype == TokenType::Char);
+        if has_char {
+            let mut types = specifiers
+                .iter()
+                .filter(|tok| !matches!(tok.ttype, TokenType::Signed | TokenType::Unsigned));
+            if let Some(tok) = types.nth(1) {
+                return Err(ParseError::InvalidSpecifiers(
+                    "two or more data types in declaration specifiers",
+                    tok.clone(),
+                ));
+            }
+        }
+        let mut has_int = false;
+        let mut has_long = false;
+        let mut signedness = None;
+        for tok in specifiers {
+            match tok.ttype {
+                TokenType::Signed | TokenType::Unsigned if signedness.is_some() => {
+                    let msg = if signedness == Some(tok.ttype) {
+                        "duplicate signedness in declaration specifiers"
+                    } else {
+                        "both 'signed' and 'unsigned' in declaration specifiers"
+                    };
+                    return Err(ParseError::InvalidSpecifiers(msg, tok));
+                }
+                TokenType::Signed | TokenType::Unsigned => signedness = Some(tok.ttype),
+                TokenType::Long if has_long => {
+                    return Err(ParseError::InvalidSpecifiers(
+                        "'long long' is not supported",
+                        tok,
+                    ))
+                }
+                TokenType::Long => has_long = true,
+                _ if has_int => {
+                    return Err(ParseError::InvalidSpecifiers(
+                        "two or more data types in declaration specifiers",
+                        tok,
+                    ))
+                }
+                _ => has_int = true,
+            }
+        }
+        if has_char {
+            return Ok(match signedness {
+                None => Type::Char,
+                Some(TokenType::Signed) => Type::SChar,
+                _ => Type::UChar,
+            });
+        }
+        let unsigned = signedness == Some(TokenType::Unsigned);
+        Ok(match (has_long, unsigned) {
+            (false, false) => Type::Int,
+            (true, false) => Type::Long,
+            (false, true) => Type::UInt,
+            (true, true) => Type::ULong,
+        })
+    }
+
+    fn function_declaration(
+        &mut self,
+        name: Token,
+        fun_type: Type,
+        params: Vec<String>,
+        storage_class: Option<StorageClass>,
+    ) -> Result<FunctionDeclaration, ParseError> {
+        let body = if self.check(TokenType::Semicolon) {
+            self.advance();
+            None
+        } else {
+            Some(self.block()?)
+        };
+        Ok(FunctionDeclaration {
+            name: name.lexeme,
+            params,
+            fun_type,
+            body,
+            storage_class,
+            line: name.line,
+        })
+    }
+
+    /// Parse a parameter list, returning the base type and the
+    /// declarator of each parameter
+    fn parameter_list(&mut self) -> Result<Vec<(Type, Declarator)>, ParseError> {
+        self.consume(TokenType::LeftParen)?;
+        let mut params = Vec::new();
+        // An empty parameter list is treated the same as '(void)'
+        if self.check(TokenType::Void) {
+            self.advance();
+        } else if !self.check(TokenType::RightParen) {
+            loop {
+                let base = self.type_specifiers()?;
+                params.push((base, self.declarator()?));
+                if !self.check(TokenType::Comma) {
+                    break;
+                }
+                self.advance();
+            }
+        }
+        self.consume(TokenType::RightParen)?;
+        Ok(params)
+    }
+
+    fn variable_declaration(
+        &mut self,
+        name: Token,
+        var_type: Type,
+        storage_class: Option<StorageClass>,
+    ) -> Result<VariableDeclaration, ParseError> {
+        let init = if self.check(TokenType::Equal) {
+            self.advance();
+            Some(self.initializer()?)
+        } else {
+            None
+        };
+        // An array declared without a size takes it from its initializer.
+        // An extern declaration may leave it incomplete, since the
+        // definition is elsewhere.
+        let var_type = match var_type {
+            Type::Array(element, 0) => {
+                let count = match &init {
+                    Some(Initializer::Compound(items, _)) => items.len(),
+                    Some(Initializer::Single(Expression {
+                        kind: ExpressionKind::String(bytes),
+                        ..
+                    })) => bytes.len() + 1,
+                    None if storage_class == Some(StorageClass::Extern) => 0,
+                    _ => return Err(ParseError::InvalidDeclarator("array size missing", name)),
+                };
+                array_of(*element, count, &name)?
+            }
+            ty => ty,
+        };
+        self.consume(TokenType::Semicolon)?;
+        Ok(VariableDeclaration {
+            name: name.lexeme,
+            var_type,
+            init,
+            storage_class,
+            line: name.line,
+        })
+    }
+
+    /// Parse an initializer: an expression or a brace-enclosed list of
+    /// initializers, which may end with a comma
+    fn initializer(&mut self) -> Result<Initializer, ParseError> {
+        if !self.check(TokenType::LeftBrace) {
+            return Ok(Initializer::Single(self.expression()?));
+        }
+        let tok = self.advance();
+        let mut items = Vec::new();
+        loop {
+            items.push(self.initializer()?);
+            if !self.check(TokenType::Comma) {
+                break;
+            }
+            self.advance();
+            if self.check(TokenType::RightBrace) {
+                break;
+            }
+        }
+        self.consume(TokenType::RightBrace)?;
+        Ok(Initializer::Compound(items, tok.line))
+    }
+
+    fn block(&mut self) -> Result<Block, ParseError> {
+        self.consume(TokenType::LeftBrace)?;
+        let mut items = Vec::new();
+        while !self.check(TokenType::RightBrace) && !self.check(TokenType::Eof) {
+            items.push(self.block_item()?);
+        }
+        self.consume(TokenType::RightBrace)?;
+        Ok(Block(items))
+    }
+
+    fn block_item(&mut self) -> Result<BlockItem, ParseError> {
+        if self.at_specifier() {
+            Ok(BlockItem::Declaration(self.declaration()?))
+        } else {
+            Ok(BlockItem::Statement(self.statement()?))
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement, ParseError> {
+        match self.current.ttype {
+            TokenType::Return => {
+                self.advance();
+                let exp = self.expression()?;
+                self.consume(TokenType::Semicolon)?;
+                Ok(Statement::Return(exp))
+            }
+            TokenType::If => {
+                self.advance();
+                self.consume(TokenType::LeftParen)?;
+                let condition = self.expression()?;
+                self.consume(TokenType::RightParen)?;
+                let then = self.statement()?;
+                // An else always belongs to the nearest if
+                let otherwise = if self.check(TokenType::Else) {
+                    self.advance();
+                    Some(Box::new(self.statement()?))
+                } else {
+                    None
+                };
+                Ok(Statement::If(condition, Box::new(then), otherwise))
+            }
+            TokenType::LeftBrace => Ok(Statement::Compound(self.block()?)),
+            TokenType::Break => {
+                let tok = self.advance();
+                self.consume(TokenType::Semicolon)?;
+                Ok(Statement::Break {
+                    label: String::new(),
+                    line: tok.line,
+                })
+            }
+            TokenType::Continue => {
+                let tok = self.advance();
+                self.consume(TokenType::Semicolon)?;
+                Ok(Statement::Continue {
+                    label: String::new(),
+                    line: tok.line,
+                })
+            }
+            TokenType::While => {
+                self.advance();
+                self.consume(TokenType::LeftParen)?;
+                let condition = self.expression()?;
+                self.consume(TokenType::RightParen)?;
+                let body = self.statement()?;
+                Ok(Statement::While {
+                    condition,
+                    body: Box::new(body),
+                    label: String::new(),
+                })
+            }
+            TokenType::Do => {
+                self.advance();
+                let body = self.statement()?;
+                self.consume(TokenType::While)?;
+                self.consume(TokenType::LeftParen)?;
+                let condition = self.expression()?;
+                self.consume(TokenType::RightParen)?;
+                self.consume(TokenType::Semicolon)?;
+                Ok(Statement::DoWhile {
+                    body: Box::new(body),
+                    condition,
+                    label: String::new(),
+                })
+            }
+            TokenType::For => {
+                self.advance();
+                self.consume(TokenType::LeftParen)?;
+                let init = if self.at_specifier() {
+                    let (base, storage_class) = self.specifiers()?;
+                    let (name, ty, _) = declare(self.declarator()?, base)?;
+                    if let Type::Function { .. } = ty {
+                        return Err(ParseError::InvalidDeclarator(
+                            "function declared in 'for' loop initializer",
+                            name,
+                        ));
+                    }
+                    ForInit::Declaration(self.variable_declaration(name, ty, storage_class)?)
+                } else {
+                    let init = self.optional_expression(TokenType::Semicolon)?;
+                    self.consume(TokenType::Semicolon)?;
+                    ForInit::Expression(init)
+                };
+                let condition = self.optional_expression(TokenType::Semicolon)?;
+                self.consume(TokenType::Semicolon)?;
+                let post = self.optional_expression(TokenType::RightParen)?;
+                self.consume(TokenType::RightParen)?;
+                let body = self.statement()?;
+                Ok(Statement::For {
+                    init: Box::new(init),
+                    condition,
+                    post,
+                    body: Box::new(body),
+                    label: String::new(),
+                })
+            }
+            TokenType::Semicolon => {
+                self.advance();
+                Ok(Statement::Null)
+            }
+            _ => {
+                let exp = self.expression()?;
+                self.consume(TokenType::Semicolon)?;
+                Ok(Statement::Expression(exp))
+            }
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, ParseError> {
+        self.binary_expression(0)
+    }
+
+    /// Parse an expression unless the next token is `end`, which marks
+    /// an empty clause
+    fn optional_expression(&mut self, end: TokenType) -> Result<Option<Expression>, ParseError> {
+        if self.check(end) {
+            Ok(None)
+        } else {
+            Ok(Some(self.expression()?))
+        }
+    }
+
+    /// Parse binary operators by precedence climbing. Only operators that
+    /// bind at least as tightly as `min_precedence` are consumed, and the
+    /// right operand must bind tighter, which makes them left associative.
+    /// Assignment and the conditional operator are the exceptions: they
+    /// are right associative.
+    fn binary_expression(&mut self, min_precedence: u8) -> Result<Expression, ParseError> {
+        let mut left = self.factor()?;
+        loop {
+            if self.check(TokenType::Question) && CONDITIONAL_PRECEDENCE >= min_precedence {
+                let tok = self.advance();
+                let middle = self.expression()?;
+                self.consume(TokenType::Colon)?;
+                let right = self.binary_expression(CONDITIONAL_PRECEDENCE)?;
+                left = Expression::new(
+                    ExpressionKind::Conditional(Box::new(left), Box::new(middle), Box::new(right)),
+                    tok.line,
+                );
+                continue;
+            }
+            if self.check(TokenType::Equal) && ASSIGNMENT_PRECEDENCE >= min_precedence {
+                let tok = self.advance();
+                let right = self.binary_expression(ASSIGNMENT_PRECEDENCE)?;
+                left = Expression::new(
+                    ExpressionKind::Assignment(Box::new(left), Box::new(right)),
+                    tok.line,
+                );
+                continue;
+            }
+            let Some((op, precedence)) = binary_operator(self.current.ttype) else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            let tok = self.advance();
+            let right = self.binary_expression(precedence + 1)?;
+            left = Expression::new(
+                ExpressionKind::Binary(op, Box::new(left), Box::new(right)),
+                tok.line,
+            );
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expression, ParseError> {
+        match self.current.ttype {
+            TokenType::Integer
+            | TokenType::LongInteger
+            | TokenType::UnsignedInteger
+            | TokenType::UnsignedLongInteger
+            | TokenType::FloatLiteral
+            | TokenType::CharLiteral => {
+                let tok = self.advance();
+                let value = constant(&tok)?;
+                self.postfix(Expression::new(ExpressionKind::Constant(value), tok.line))
+            }
+            TokenType::StringLiteral => {
+                // Adjacent string literals are concatenated
+                let tok = self.advance();
+                let mut bytes: Vec<u8> = tok.lexeme.chars().map(|c| c as u8).collect();
+                while self.check(TokenType::StringLiteral) {
+                    bytes.extend(self.advance().lexeme.chars().map(|c| c as u8));
+                }
+                self.postfix(Expression::new(ExpressionKind::String(bytes), tok.line))
+            }
+            TokenType::Identifier => {
+                let tok = self.advance();
+                let kind = if self.check(TokenType::LeftParen) {
+                    ExpressionKind::FunctionCall(tok.lexeme, self.argument_list()?)
+                } else {
+                    ExpressionKind::Var(tok.lexeme)
+                };
+                self.postfix(Expression::new(kind, tok.line))
+            }
+            TokenType::Star | TokenType::Ampersand => {
+                let tok = self.advance();
+                let operand = Box::new(self.factor()?);
+                let kind = if tok.ttype == TokenType::Star {
+                    ExpressionKind::Dereference(operand)
+                } else {
+                    ExpressionKind::AddressOf(operand)
+                };
+                Ok(Expression::new(kind, tok.line))
+            }
+            TokenType::Minus | TokenType::Tilde | TokenType::Bang => {
+                let tok = self.advance();
+                let op = match tok.ttype {
+                    TokenType::Minus => UnaryOperator::Negate,
+                    TokenType::Tilde => UnaryOperator::Complement,
+                    _ => UnaryOperator::Not,
+                };
+                let operand = self.factor()?;
+                Ok(Expression::new(
+                    ExpressionKind::Unary(op, Box::new(operand)),
+                    tok.line,
+                ))
+            }
+            TokenType::LeftParen => {
+                let tok = self.advance();
+                if self.at_type_specifier() {
+                    let ty = self.type_name()?;
+                    self.consume(TokenType::RightParen)?;
+                    let operand = self.factor()?;
+                    return Ok(Expression::new(
+                        ExpressionKind::Cast(ty, Box::new(operand)),
+                        tok.line,
+                    ));
+                }
+                let exp = self.expression()?;
+                self.consume(TokenType::RightParen)?;
+                self.postfix(exp)
+            }
+            _ => Err(ParseError::ExpectedConstruct(
+                "expression",
+                self.current.clone(),
+            )),
+        }
+    }
+
+    /// Parse any subscripts following a primary expression. They bind
+    /// more tightly than the unary operators.
+    fn postfix(&mut self, mut exp: Expression) -> Result<Expression, ParseError> {
+        while self.check(TokenType::LeftBracket) {
+            let tok = self.advance();
+            let index = self.expression()?;
+            self.consume(TokenType::RightBracket)?;
+            exp = Expression::new(
+                ExpressionKind::Subscript(Box::new(exp), Box::new(index)),
+                tok.line,
+            );
+        }
+        Ok(exp)
+    }
+
+    fn argument_list(&mut self) -> Result<Vec<Expression>, ParseError> {
+        self.consume(TokenType::LeftParen)?;
+        let mut args = Vec::new();
+        if !self.check(TokenType::RightParen) {
+            loop {
+                args.push(self.expression()?);
+                if !self.check(TokenType::Comma) {
+                    break;
+                }
+                self.advance();
+            }
+        }
+        self.consume(TokenType::RightParen)?;
+        Ok(args)
+    }
+
+    /// Move to the next token and return the one that was current
+    fn advance(&mut self) -> Token {
+        let next = self.scanner.scan_token();
+        std::mem::replace(&mut self.current, next)
+    }
+
+    fn check(&self, ttype: TokenType) -> bool {
+        self.current.ttype == ttype
+    }
+
+    fn consume(&mut self, ttype: TokenType) -> Result<Token, ParseError> {
+        if self.check(ttype) {
+            Ok(self.advance())
+        } else {
+            Err(ParseError::Expected(ttype, self.current.clone()))
+        }
+    }
+}
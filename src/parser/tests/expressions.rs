@@ -0,0 +1,225 @@
+use super::*;
+
+#[cfg(test)]
+fn constant(value: i32, line: usize) -> Box<Expression> {
+    Box::new(Expression::new(
+        ExpressionKind::Constant(Const::Int(value)),
+        line,
+    ))
+}
+
+#[test]
+fn test_unary_operators() {
+    let program = parse("int main(void) { return -~!5; }");
+    let not = Expression::new(ExpressionKind::Unary(UnaryOperator::Not, constant(5, 1)), 1);
+    let complement = Expression::new(
+        ExpressionKind::Unary(UnaryOperator::Complement, Box::new(not)),
+        1,
+    );
+    let negate = Expression::new(
+        ExpressionKind::Unary(UnaryOperator::Negate, Box::new(complement)),
+        1,
+    );
+    assert_eq!(return_value(&program), &negate);
+}
+
+#[test]
+fn test_parenthesized_unary() {
+    let program = parse("int main(void) { return -(-2); }");
+    let inner = Expression::new(
+        ExpressionKind::Unary(UnaryOperator::Negate, constant(2, 1)),
+        1,
+    );
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::Unary(UnaryOperator::Negate, Box::new(inner))
+    );
+}
+
+#[test]
+fn test_decrement_is_not_negation() {
+    let err = parse_error("int main(void) { return --2; }");
+    assert_eq!(err.to_string(), "expected expression, found '--'");
+}
+
+#[test]
+fn test_missing_operand() {
+    let err = parse_error("int main(void) { return ~; }");
+    assert_eq!(err.to_string(), "expected expression, found ';'");
+}
+
+#[cfg(test)]
+fn binary(op: BinaryOperator, left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::new(ExpressionKind::Binary(op, left, right), 1))
+}
+
+#[test]
+fn test_left_associative() {
+    let program = parse("int main(void) { return 1 - 2 - 3; }");
+    let expected = binary(
+        BinaryOperator::Subtract,
+        binary(BinaryOperator::Subtract, constant(1, 1), constant(2, 1)),
+        constant(3, 1),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_multiplicative_precedence() {
+    let program = parse("int main(void) { return 1 + 2 * 3 % 4; }");
+    let expected = binary(
+        BinaryOperator::Add,
+        constant(1, 1),
+        binary(
+            BinaryOperator::Remainder,
+            binary(BinaryOperator::Multiply, constant(2, 1), constant(3, 1)),
+            constant(4, 1),
+        ),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_parentheses_override_precedence() {
+    let program = parse("int main(void) { return (1 + 2) / -3; }");
+    let expected = binary(
+        BinaryOperator::Divide,
+        binary(BinaryOperator::Add, constant(1, 1), constant(2, 1)),
+        Box::new(Expression::new(
+            ExpressionKind::Unary(UnaryOperator::Negate, constant(3, 1)),
+            1,
+        )),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_missing_right_operand() {
+    let err = parse_error("int main(void) { return 1 + ; }");
+    assert_eq!(err.to_string(), "expected expression, found ';'");
+}
+
+#[test]
+fn test_missing_operator() {
+    let err = parse_error("int main(void) { return 1 2; }");
+    assert_eq!(err.to_string(), "expected ';', found integer '2'");
+}
+
+#[test]
+fn test_bitwise_precedence() {
+    // '&' binds tighter than '^', which binds tighter than '|'
+    let program = parse("int main(void) { return 1 | 2 ^ 3 & 4; }");
+    let expected = binary(
+        BinaryOperator::BitwiseOr,
+        constant(1, 1),
+        binary(
+            BinaryOperator::BitwiseXor,
+            constant(2, 1),
+            binary(BinaryOperator::BitwiseAnd, constant(3, 1), constant(4, 1)),
+        ),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_shift_precedence() {
+    // Shifts bind looser than additive operators but tighter than '&'
+    let program = parse("int main(void) { return 1 << 2 + 3 & 4; }");
+    let expected = binary(
+        BinaryOperator::BitwiseAnd,
+        binary(
+            BinaryOperator::ShiftLeft,
+            constant(1, 1),
+            binary(BinaryOperator::Add, constant(2, 1), constant(3, 1)),
+        ),
+        constant(4, 1),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_logical_and_relational_precedence() {
+    let program = parse("int main(void) { return 1 == 2 || 3 < 4 && 5 != 6; }");
+    let expected = binary(
+        BinaryOperator::Or,
+        binary(BinaryOperator::Equal, constant(1, 1), constant(2, 1)),
+        binary(
+            BinaryOperator::And,
+            binary(BinaryOperator::LessThan, constant(3, 1), constant(4, 1)),
+            binary(BinaryOperator::NotEqual, constant(5, 1), constant(6, 1)),
+        ),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_relational_binds_tighter_than_equality() {
+    let program = parse("int main(void) { return 1 < 2 == 3 >= 4; }");
+    let expected = binary(
+        BinaryOperator::Equal,
+        binary(BinaryOperator::LessThan, constant(1, 1), constant(2, 1)),
+        binary(
+            BinaryOperator::GreaterOrEqual,
+            constant(3, 1),
+            constant(4, 1),
+        ),
+    );
+    assert_eq!(return_value(&program), expected.as_ref());
+}
+
+#[test]
+fn test_dereference_and_address_of() {
+    let program = parse("int main(void) { return *&x * **p; }");
+    let ExpressionKind::Binary(BinaryOperator::Multiply, left, right) =
+        &return_value(&program).kind
+    else {
+        panic!("expected a multiplication");
+    };
+    let ExpressionKind::Dereference(inner) = &left.kind else {
+        panic!("expected a dereference");
+    };
+    assert!(
+        matches!(&inner.kind, ExpressionKind::AddressOf(x) if x.kind == ExpressionKind::Var("x".to_string()))
+    );
+    let ExpressionKind::Dereference(inner) = &right.kind else {
+        panic!("expected a dereference");
+    };
+    assert!(matches!(&inner.kind, ExpressionKind::Dereference(_)));
+}
+
+#[test]
+fn test_subscripts() {
+    let program = parse("int main(void) { return -a[1][i] + (p)[2]; }");
+    let ExpressionKind::Binary(BinaryOperator::Add, left, right) = &return_value(&program).kind
+    else {
+        panic!("expected an addition");
+    };
+    // Subscripts bind more tightly than unary operators
+    let ExpressionKind::Unary(UnaryOperator::Negate, inner) = &left.kind else {
+        panic!("expected a negation");
+    };
+    let ExpressionKind::Subscript(array, index) = &inner.kind else {
+        panic!("expected a subscript");
+    };
+    assert_eq!(index.kind, ExpressionKind::Var("i".to_string()));
+    assert!(matches!(&array.kind, ExpressionKind::Subscript(a, one)
+        if a.kind == ExpressionKind::Var("a".to_string())
+            && one.kind == ExpressionKind::Constant(Const::Int(1))));
+    assert!(matches!(&right.kind, ExpressionKind::Subscript(..)));
+}
+
+#[test]
+fn test_string_literals() {
+    let program = parse(r#"int main(void) { return "foo" "\tbar\0" ""; }"#);
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::String(b"foo\tbar\0".to_vec())
+    );
+    let program = parse(r#"int main(void) { return "abc"[1]; }"#);
+    let ExpressionKind::Subscript(string, _) = &return_value(&program).kind else {
+        panic!("expected a subscript");
+    };
+    assert_eq!(string.kind, ExpressionKind::String(b"abc".to_vec()));
+    let err = parse_error(r#"int main(void) { return 1 "a\n"; }"#);
+    assert_eq!(err.to_string(), r#"expected ';', found string "a\n""#);
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+#[test]
+fn test_multiple_functions() {
+    let input = r#"
+        int add(int a, int b) {
+            return a + b;
+        }
+
+        int main(void) {
+            return add(1, 2);
+        }
+    "#;
+    let program = parse(input);
+    assert_eq!(functions(&program).len(), 2);
+    assert_eq!(functions(&program)[0].name, "add");
+    assert_eq!(functions(&program)[0].params, vec!["a", "b"]);
+    assert_eq!(functions(&program)[0].line, 2);
+    assert_eq!(functions(&program)[1].params, Vec::<String>::new());
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::FunctionCall(
+            "add".to_string(),
+            vec![
+                Expression::new(ExpressionKind::Constant(Const::Int(1)), 7),
+                Expression::new(ExpressionKind::Constant(Const::Int(2)), 7),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_function_declaration_without_body() {
+    let program = parse("int putchar(int c); int main(void) { return 0; }");
+    assert_eq!(functions(&program)[0].name, "putchar");
+    assert!(functions(&program)[0].body.is_none());
+}
+
+#[test]
+fn test_local_function_declaration() {
+    let program = parse("int main(void) { int f(void); return f(); }");
+    assert!(matches!(
+        &body(&program)[0],
+        BlockItem::Declaration(Declaration::Function(FunctionDeclaration { name, body: None, .. }))
+            if name == "f"
+    ));
+}
+
+#[test]
+fn test_call_without_arguments_in_expression() {
+    let program = parse("int main(void) { return -f() * 2; }");
+    let ExpressionKind::Binary(BinaryOperator::Multiply, left, _) = &return_value(&program).kind
+    else {
+        panic!("expected a multiplication");
+    };
+    let ExpressionKind::Unary(UnaryOperator::Negate, operand) = &left.kind else {
+        panic!("expected a negation");
+    };
+    assert_eq!(
+        operand.kind,
+        ExpressionKind::FunctionCall("f".to_string(), Vec::new())
+    );
+}
+
+#[test]
+fn test_trailing_comma_in_arguments() {
+    let err = parse_error("int main(void) { return f(1, ); }");
+    assert_eq!(err.to_string(), "expected expression, found ')'");
+}
+
+#[test]
+fn test_parameter_requires_type() {
+    let err = parse_error("int f(a) { return a; }");
+    assert_eq!(err.to_string(), "expected 'int', found identifier 'a'");
+}
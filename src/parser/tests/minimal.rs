@@ -0,0 +1,81 @@
+use super::*;
+
+#[test]
+fn test_return_constant() {
+    let input = r#"
+        int main(void) {
+            return 2;
+        }
+    "#;
+    let program = parse(input);
+    assert_eq!(functions(&program)[0].name, "main");
+    assert_eq!(functions(&program)[0].line, 2);
+    assert_eq!(
+        return_value(&program),
+        &Expression::new(ExpressionKind::Constant(Const::Int(2)), 3)
+    );
+}
+
+#[test]
+fn test_empty_parameter_list() {
+    let program = parse("int main() { return 0; }");
+    assert_eq!(functions(&program)[0].name, "main");
+}
+
+#[test]
+fn test_parenthesized_constant() {
+    let program = parse("int main(void) { return ((100)); }");
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::Constant(Const::Int(100))
+    );
+}
+
+#[test]
+fn test_missing_semicolon() {
+    let input = r#"int main(void) {
+        return 2
+    }"#;
+    let err = parse_error(input);
+    assert!(
+        matches!(&err, ParseError::Expected(TokenType::Semicolon, tok) if tok.ttype == TokenType::RightBrace)
+    );
+    assert_eq!(err.line(), 3);
+    assert_eq!(err.to_string(), "expected ';', found '}'");
+}
+
+#[test]
+fn test_missing_return_value() {
+    let err = parse_error("int main(void) { return; }");
+    assert_eq!(err.to_string(), "expected expression, found ';'");
+}
+
+#[test]
+fn test_missing_type() {
+    let err = parse_error("main(void) { return 0; }");
+    assert_eq!(err.to_string(), "expected 'int', found identifier 'main'");
+}
+
+#[test]
+fn test_trailing_tokens() {
+    let err = parse_error("int main(void) { return 0; } foo");
+    assert_eq!(err.to_string(), "expected 'int', found identifier 'foo'");
+}
+
+#[test]
+fn test_unclosed_function() {
+    let err = parse_error("int main(void) { return 0;");
+    assert_eq!(err.to_string(), "expected '}', found end of file");
+}
+
+#[test]
+fn test_constant_too_large() {
+    let err = parse_error("int main(void) { return 9223372036854775808; }");
+    assert!(matches!(err, ParseError::InvalidConstant(_)));
+}
+
+#[test]
+fn test_scanner_error() {
+    let err = parse_error("int main(void) { return 0@1; }");
+    assert_eq!(err.to_string(), "Unexpected character @");
+}
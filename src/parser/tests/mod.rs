@@ -0,0 +1,55 @@
+use super::*;
+
+mod expressions;
+mod functions;
+mod minimal;
+mod statements;
+mod types;
+
+#[cfg(test)]
+fn parse(input: &str) -> Program {
+    match Parser::new(input).parse_program() {
+        Ok(program) => program,
+        Err(e) => panic!("parse failed at line {}: {}", e.line(), e),
+    }
+}
+
+#[cfg(test)]
+fn parse_error(input: &str) -> ParseError {
+    match Parser::new(input).parse_program() {
+        Ok(program) => panic!("expected a parse error, got {:?}", program),
+        Err(e) => e,
+    }
+}
+
+/// The body of the last function in the program
+#[cfg(test)]
+fn body(program: &Program) -> &[BlockItem] {
+    match functions(program).last() {
+        Some(FunctionDeclaration {
+            body: Some(body), ..
+        }) => &body.0,
+        function => panic!("expected a function definition, got {:?}", function),
+    }
+}
+
+#[cfg(test)]
+fn return_value(program: &Program) -> &Expression {
+    match body(program).last() {
+        Some(BlockItem::Statement(Statement::Return(exp))) => exp,
+        item => panic!("expected a return statement, got {:?}", item),
+    }
+}
+
+/// The function declarations in the program, in source order
+#[cfg(test)]
+fn functions(program: &Program) -> Vec<&FunctionDeclaration> {
+    program
+        .declarations
+        .iter()
+        .filter_map(|decl| match decl {
+            Declaration::Function(function) => Some(function),
+            Declaration::Variable(_) => None,
+        })
+        .collect()
+}
@@ -0,0 +1,321 @@
+use super::*;
+
+#[test]
+fn test_declarations() {
+    let input = r#"int main(void) {
+        int a;
+        int b = 2;
+        return b;
+    }"#;
+    let program = parse(input);
+    assert_eq!(
+        body(&program)[0],
+        BlockItem::Declaration(Declaration::Variable(VariableDeclaration {
+            name: "a".to_string(),
+            var_type: Type::Int,
+            init: None,
+            storage_class: None,
+            line: 2,
+        }))
+    );
+    assert_eq!(
+        body(&program)[1],
+        BlockItem::Declaration(Declaration::Variable(VariableDeclaration {
+            name: "b".to_string(),
+            var_type: Type::Int,
+            init: Some(Initializer::Single(Expression::new(
+                ExpressionKind::Constant(Const::Int(2)),
+                3
+            ))),
+            storage_class: None,
+            line: 3,
+        }))
+    );
+}
+
+#[test]
+fn test_expression_and_null_statements() {
+    let program = parse("int main(void) { a = 1; ; return 0; }");
+    let assignment = Expression::new(
+        ExpressionKind::Assignment(
+            Box::new(Expression::new(ExpressionKind::Var("a".to_string()), 1)),
+            Box::new(Expression::new(ExpressionKind::Constant(Const::Int(1)), 1)),
+        ),
+        1,
+    );
+    assert_eq!(
+        body(&program)[0],
+        BlockItem::Statement(Statement::Expression(assignment))
+    );
+    assert_eq!(body(&program)[1], BlockItem::Statement(Statement::Null));
+}
+
+#[test]
+fn test_empty_function_body() {
+    let program = parse("int main(void) {}");
+    assert!(body(&program).is_empty());
+}
+
+#[test]
+fn test_assignment_is_right_associative() {
+    let program = parse("int main(void) { return a = b = 3 + 1; }");
+    let var = |name: &str| Box::new(Expression::new(ExpressionKind::Var(name.to_string()), 1));
+    let sum = Expression::new(
+        ExpressionKind::Binary(
+            BinaryOperator::Add,
+            Box::new(Expression::new(ExpressionKind::Constant(Const::Int(3)), 1)),
+            Box::new(Expression::new(ExpressionKind::Constant(Const::Int(1)), 1)),
+        ),
+        1,
+    );
+    let inner = Expression::new(ExpressionKind::Assignment(var("b"), Box::new(sum)), 1);
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::Assignment(var("a"), Box::new(inner))
+    );
+}
+
+#[test]
+fn test_assignment_binds_loosest() {
+    let program = parse("int main(void) { return a = 1 || 0; }");
+    assert!(matches!(
+        &return_value(&program).kind,
+        ExpressionKind::Assignment(_, right)
+            if matches!(right.kind, ExpressionKind::Binary(BinaryOperator::Or, _, _))
+    ));
+}
+
+#[test]
+fn test_declaration_missing_name() {
+    let err = parse_error("int main(void) { int = 3; return 0; }");
+    assert_eq!(err.to_string(), "expected 'identifier', found '='");
+}
+
+#[test]
+fn test_declaration_as_expression() {
+    let err = parse_error("int main(void) { return int a; }");
+    assert_eq!(err.to_string(), "expected expression, found 'int'");
+}
+
+#[test]
+fn test_dangling_else() {
+    let program = parse("int main(void) { if (1) if (2) return 3; else return 4; }");
+    let BlockItem::Statement(Statement::If(_, inner, None)) = &body(&program)[0] else {
+        panic!("expected an if statement without else");
+    };
+    assert!(matches!(inner.as_ref(), Statement::If(_, _, Some(_))));
+}
+
+#[test]
+fn test_if_else_chain() {
+    let program = parse("int main(void) { if (a) b = 1; else if (c) b = 2; else b = 3; }");
+    let BlockItem::Statement(Statement::If(_, _, Some(otherwise))) = &body(&program)[0] else {
+        panic!("expected an if statement with else");
+    };
+    assert!(matches!(otherwise.as_ref(), Statement::If(_, _, Some(_))));
+}
+
+#[test]
+fn test_conditional_is_right_associative() {
+    let program = parse("int main(void) { return a ? 1 : b ? 2 : 3; }");
+    let ExpressionKind::Conditional(_, _, otherwise) = &return_value(&program).kind else {
+        panic!("expected a conditional expression");
+    };
+    assert!(matches!(otherwise.kind, ExpressionKind::Conditional(..)));
+}
+
+#[test]
+fn test_conditional_precedence() {
+    // The conditional binds tighter than assignment but looser than '||'
+    let program = parse("int main(void) { return x = a || b ? c = 1 : 2; }");
+    let ExpressionKind::Assignment(_, right) = &return_value(&program).kind else {
+        panic!("expected an assignment");
+    };
+    let ExpressionKind::Conditional(condition, middle, _) = &right.kind else {
+        panic!("expected a conditional expression");
+    };
+    assert!(matches!(
+        condition.kind,
+        ExpressionKind::Binary(BinaryOperator::Or, _, _)
+    ));
+    assert!(matches!(middle.kind, ExpressionKind::Assignment(..)));
+}
+
+#[test]
+fn test_conditional_missing_colon() {
+    let err = parse_error("int main(void) { return 1 ? 2; }");
+    assert_eq!(err.to_string(), "expected ':', found ';'");
+}
+
+#[test]
+fn test_else_without_if() {
+    let err = parse_error("int main(void) { else return 1; }");
+    assert_eq!(err.to_string(), "expected expression, found 'else'");
+}
+
+#[test]
+fn test_compound_statements() {
+    let program = parse("int main(void) { { int a = 1; { } } if (1) { return 2; } }");
+    let body = &body(&program);
+    let BlockItem::Statement(Statement::Compound(outer)) = &body[0] else {
+        panic!("expected a compound statement");
+    };
+    assert!(matches!(outer.0[0], BlockItem::Declaration(_)));
+    assert_eq!(
+        outer.0[1],
+        BlockItem::Statement(Statement::Compound(Block(vec![])))
+    );
+    let BlockItem::Statement(Statement::If(_, then, None)) = &body[1] else {
+        panic!("expected an if statement");
+    };
+    assert!(matches!(then.as_ref(), Statement::Compound(_)));
+}
+
+#[test]
+fn test_unclosed_block() {
+    let err = parse_error("int main(void) { { return 0; }");
+    assert_eq!(err.to_string(), "expected '}', found end of file");
+}
+
+#[test]
+fn test_while_and_do_while() {
+    let program = parse("int main(void) { while (a) ; do ; while (b); }");
+    assert_eq!(
+        body(&program)[0],
+        BlockItem::Statement(Statement::While {
+            condition: Expression::new(ExpressionKind::Var("a".to_string()), 1),
+            body: Box::new(Statement::Null),
+            label: String::new(),
+        })
+    );
+    assert_eq!(
+        body(&program)[1],
+        BlockItem::Statement(Statement::DoWhile {
+            body: Box::new(Statement::Null),
+            condition: Expression::new(ExpressionKind::Var("b".to_string()), 1),
+            label: String::new(),
+        })
+    );
+}
+
+#[test]
+fn test_for_with_declaration() {
+    let program = parse("int main(void) { for (int i = 0; i; ) break; }");
+    let BlockItem::Statement(Statement::For {
+        init,
+        condition: Some(_),
+        post: None,
+        body,
+        ..
+    }) = &body(&program)[0]
+    else {
+        panic!("expected a for loop, got {:?}", body(&program)[0]);
+    };
+    let ForInit::Declaration(decl) = init.as_ref() else {
+        panic!("expected a declaration, got {:?}", init);
+    };
+    assert_eq!(decl.name, "i");
+    assert!(matches!(**body, Statement::Break { line: 1, .. }));
+}
+
+#[test]
+fn test_for_with_empty_clauses() {
+    let program = parse("int main(void) { for (;;) continue; }");
+    let BlockItem::Statement(Statement::For {
+        init,
+        condition: None,
+        post: None,
+        ..
+    }) = &body(&program)[0]
+    else {
+        panic!("expected a for loop, got {:?}", body(&program)[0]);
+    };
+    assert_eq!(**init, ForInit::Expression(None));
+}
+
+#[test]
+fn test_do_while_requires_semicolon() {
+    let e = parse_error("int main(void) { do ; while (1) }");
+    assert_eq!(e.to_string(), "expected ';', found '}'");
+}
+
+#[test]
+fn test_storage_class_specifiers() {
+    let program =
+        parse("static int x = 1; int extern y; int main(void) { static int z; return 0; }");
+    assert!(matches!(
+        &program.declarations[0],
+        Declaration::Variable(VariableDeclaration {
+            storage_class: Some(StorageClass::Static),
+            init: Some(_),
+            ..
+        })
+    ));
+    assert!(matches!(
+        &program.declarations[1],
+        Declaration::Variable(VariableDeclaration {
+            storage_class: Some(StorageClass::Extern),
+            init: None,
+            ..
+        })
+    ));
+    assert!(matches!(
+        &body(&program)[0],
+        BlockItem::Declaration(Declaration::Variable(VariableDeclaration {
+            storage_class: Some(StorageClass::Static),
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn test_static_function() {
+    let program = parse("static int f(void) { return 1; }");
+    assert_eq!(
+        functions(&program)[0].storage_class,
+        Some(StorageClass::Static)
+    );
+}
+
+#[test]
+fn test_invalid_specifiers() {
+    let err = parse_error("static extern int x;");
+    assert_eq!(
+        err.to_string(),
+        "multiple storage classes in declaration specifiers"
+    );
+    let err = parse_error("int main(void) {\n int static int x;\n}");
+    assert_eq!(err.line(), 2);
+    assert_eq!(
+        err.to_string(),
+        "two or more data types in declaration specifiers"
+    );
+    let err = parse_error("static x;");
+    assert_eq!(err.to_string(), "expected 'int', found identifier 'x'");
+}
+
+#[test]
+fn test_initializer_lists() {
+    let program = parse("int main(void) { int a[2][2] = {{1, 2}, {3,},}; return 0; }");
+    let BlockItem::Declaration(Declaration::Variable(decl)) = &body(&program)[0] else {
+        panic!("expected a declaration");
+    };
+    let single = |value| {
+        Initializer::Single(Expression::new(
+            ExpressionKind::Constant(Const::Int(value)),
+            1,
+        ))
+    };
+    assert_eq!(
+        decl.init,
+        Some(Initializer::Compound(
+            vec![
+                Initializer::Compound(vec![single(1), single(2)], 1),
+                Initializer::Compound(vec![single(3)], 1),
+            ],
+            1
+        ))
+    );
+    let err = parse_error("int main(void) { int a[2] = {}; return 0; }");
+    assert_eq!(err.to_string(), "expected expression, found '}'");
+}
@@ -0,0 +1,392 @@
+use super::*;
+
+#[test]
+fn test_long_specifiers() {
+    let program = parse("long a; long int b; int long c; static long d; long f(long x, int y);");
+    for decl in &program.declarations[..4] {
+        let Declaration::Variable(decl) = decl else {
+            panic!("expected a variable declaration");
+        };
+        assert_eq!(decl.var_type, Type::Long);
+    }
+    assert_eq!(
+        functions(&program)[0].fun_type,
+        Type::Function {
+            params: vec![Type::Long, Type::Int],
+            ret: Box::new(Type::Long),
+        }
+    );
+}
+
+#[test]
+fn test_long_constants() {
+    let program = parse("long main(void) { return 3l; }");
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::Constant(Const::Long(3))
+    );
+    // An int constant that does not fit in 32 bits is a long
+    let program = parse("int main(void) { return 2147483648; }");
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::Constant(Const::Long(2147483648))
+    );
+    let program = parse("int main(void) { return 2147483647; }");
+    assert_eq!(
+        return_value(&program).kind,
+        ExpressionKind::Constant(Const::Int(2147483647))
+    );
+}
+
+#[test]
+fn test_cast() {
+    let program = parse("int main(void) { return (long) 1 + (int) 2l; }");
+    let ExpressionKind::Binary(BinaryOperator::Add, left, right) = &return_value(&program).kind
+    else {
+        panic!("expected an addition");
+    };
+    assert_eq!(
+        left.kind,
+        ExpressionKind::Cast(
+            Type::Long,
+            Box::new(Expression::new(ExpressionKind::Constant(Const::Int(1)), 1))
+        )
+    );
+    assert!(matches!(&right.kind, ExpressionKind::Cast(Type::Int, _)));
+}
+
+#[test]
+fn test_invalid_type_specifiers() {
+    let err = parse_error("long long x;");
+    assert_eq!(err.to_string(), "'long long' is not supported");
+    let err = parse_error("int main(void) { return (static int) 1; }");
+    assert_eq!(err.line(), 1);
+}
+
+#[test]
+fn test_unsigned_specifiers() {
+    let program = parse(
+        "unsigned a; unsigned int b; int unsigned c; long unsigned d; unsigned long int e; signed f; signed long g;",
+    );
+    let types: Vec<&Type> = program
+        .declarations
+        .iter()
+        .map(|decl| match decl {
+            Declaration::Variable(decl) => &decl.var_type,
+            Declaration::Function(_) => panic!("expected a variable declaration"),
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            &Type::UInt,
+            &Type::UInt,
+            &Type::UInt,
+            &Type::ULong,
+            &Type::ULong,
+            &Type::Int,
+            &Type::Long,
+        ]
+    );
+    let err = parse_error("signed unsigned x;");
+    assert_eq!(
+        err.to_string(),
+        "both 'signed' and 'unsigned' in declaration specifiers"
+    );
+    let err = parse_error("unsigned int unsigned x;");
+    assert_eq!(
+        err.to_string(),
+        "duplicate signedness in declaration specifiers"
+    );
+}
+
+#[test]
+fn test_unsigned_constants() {
+    let cases = [
+        ("4294967295u", Const::UInt(4294967295)),
+        ("4294967296u", Const::ULong(4294967296)),
+        ("1ul", Const::ULong(1)),
+        ("18446744073709551615UL", Const::ULong(u64::MAX)),
+    ];
+    for (source, expected) in cases {
+        let program = parse(&format!("int main(void) {{ return {}; }}", source));
+        assert_eq!(
+            return_value(&program).kind,
+            ExpressionKind::Constant(expected)
+        );
+    }
+    let err = parse_error("int main(void) { return 18446744073709551616u; }");
+    assert_eq!(
+        err.to_string(),
+        "integer constant '18446744073709551616u' is too large"
+    );
+}
+
+#[test]
+fn test_double_specifiers_and_constants() {
+    let program = parse("double d; double f(double x, int y);");
+    let Declaration::Variable(decl) = &program.declarations[0] else {
+        panic!("expected a variable declaration");
+    };
+    assert_eq!(decl.var_type, Type::Double);
+    assert_eq!(
+        functions(&program)[0].fun_type,
+        Type::Function {
+            params: vec![Type::Double, Type::Int],
+            ret: Box::new(Type::Double),
+        }
+    );
+    let cases = [
+        ("1.5", 1.5),
+        (".25", 0.25),
+        ("3.", 3.0),
+        ("1e10", 1e10),
+        ("2.5E-3", 2.5e-3),
+    ];
+    for (source, expected) in cases {
+        let program = parse(&format!("double main(void) {{ return {}; }}", source));
+        assert_eq!(
+            return_value(&program).kind,
+            ExpressionKind::Constant(Const::Double(expected))
+        );
+    }
+    let err = parse_error("long double x;");
+    assert_eq!(
+        err.to_string(),
+        "two or more data types in declaration specifiers"
+    );
+}
+
+#[test]
+fn test_char_specifiers_and_constants() {
+    let program = parse("char a; signed char b; char signed c; unsigned char d; char unsigned e;");
+    let types: Vec<&Type> = program
+        .declarations
+        .iter()
+        .map(|decl| match decl {
+            Declaration::Variable(decl) => &decl.var_type,
+            Declaration::Function(_) => panic!("expected a variable declaration"),
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            &Type::Char,
+            &Type::SChar,
+            &Type::SChar,
+            &Type::UChar,
+            &Type::UChar,
+        ]
+    );
+    // Character constants have type int, with the value of a signed char
+    let cases = [
+        (r"'a'", 97),
+        (r"'\n'", 10),
+        (r"'\x41'", 65),
+        (r"'\377'", -1),
+    ];
+    for (source, expected) in cases {
+        let program = parse(&format!("int main(void) {{ return {}; }}", source));
+        assert_eq!(
+            return_value(&program).kind,
+            ExpressionKind::Constant(Const::Int(expected))
+        );
+    }
+    for source in [
+        "char int x;",
+        "long char x;",
+        "char char x;",
+        "char double x;",
+    ] {
+        let err = parse_error(source);
+        assert_eq!(
+            err.to_string(),
+            "two or more data types in declaration specifiers",
+            "in {}",
+            source
+        );
+    }
+    let err = parse_error("int main(void) { return 'a' 'b'; }");
+    assert_eq!(
+        err.to_string(),
+        "expected ';', found character constant 'b'"
+    );
+}
+
+#[test]
+fn test_pointer_declarators() {
+    let program = parse("int *p; long **pp; double (*d); int *f(int *a, int b); int main(void);");
+    let types: Vec<&Type> = program
+        .declarations
+        .iter()
+        .map(|decl| match decl {
+            Declaration::Variable(decl) => &decl.var_type,
+            Declaration::Function(function) => &function.fun_type,
+        })
+        .collect();
+    let pointer = |ty| Type::Pointer(Box::new(ty));
+    assert_eq!(types[0], &pointer(Type::Int));
+    assert_eq!(types[1], &pointer(pointer(Type::Long)));
+    assert_eq!(types[2], &pointer(Type::Double));
+    assert_eq!(
+        types[3],
+        &Type::Function {
+            params: vec![pointer(Type::Int), Type::Int],
+            ret: Box::new(pointer(Type::Int)),
+        }
+    );
+    assert_eq!(functions(&program)[0].params, vec!["a", "b"]);
+}
+
+#[test]
+fn test_pointer_casts() {
+    let program = parse("int main(void) { return (long **) 0 == (long *(*)) 0; }");
+    let ExpressionKind::Binary(_, left, right) = &return_value(&program).kind else {
+        panic!("expected a comparison");
+    };
+    let expected = Type::Pointer(Box::new(Type::Pointer(Box::new(Type::Long))));
+    assert!(matches!(&left.kind, ExpressionKind::Cast(ty, _) if *ty == expected));
+    assert!(matches!(&right.kind, ExpressionKind::Cast(ty, _) if *ty == expected));
+}
+
+#[test]
+fn test_function_pointers_are_rejected() {
+    let err = parse_error("int (*f)(void);");
+    assert_eq!(err.to_string(), "function pointers are not supported");
+    let err = parse_error("int main(void) { for (int f(void); ; ) ; }");
+    assert_eq!(
+        err.to_string(),
+        "function declared in 'for' loop initializer"
+    );
+}
+
+#[test]
+fn test_array_declarators() {
+    let program = parse("int a[3]; long *b[2][4]; int (*c)[5]; int f(int x[2][3]);");
+    let types: Vec<&Type> = program
+        .declarations
+        .iter()
+        .map(|decl| match decl {
+            Declaration::Variable(decl) => &decl.var_type,
+            Declaration::Function(function) => &function.fun_type,
+        })
+        .collect();
+    let pointer = |ty| Type::Pointer(Box::new(ty));
+    let array = |ty, count| Type::Array(Box::new(ty), count);
+    assert_eq!(types[0], &array(Type::Int, 3));
+    assert_eq!(types[1], &array(array(pointer(Type::Long), 4), 2));
+    assert_eq!(types[2], &pointer(array(Type::Int, 5)));
+    // An array parameter is adjusted to a pointer to its first element
+    assert_eq!(
+        types[3],
+        &Type::Function {
+            params: vec![pointer(array(Type::Int, 3))],
+            ret: Box::new(Type::Int),
+        }
+    );
+    assert_eq!(types[1].to_string(), "long *[2][4]");
+}
+
+#[test]
+fn test_array_casts() {
+    let program = parse("int main(void) { return (int (*)[3]) 0 == (int *[2]) 0; }");
+    let ExpressionKind::Binary(_, left, right) = &return_value(&program).kind else {
+        panic!("expected a comparison");
+    };
+    let int_array = Type::Array(Box::new(Type::Int), 3);
+    assert!(matches!(&left.kind, ExpressionKind::Cast(Type::Pointer(ty), _) if **ty == int_array));
+    let pointer_array = Type::Array(Box::new(Type::Pointer(Box::new(Type::Int))), 2);
+    assert!(matches!(&right.kind, ExpressionKind::Cast(ty, _) if *ty == pointer_array));
+}
+
+#[test]
+fn test_omitted_array_size() {
+    let program =
+        parse(r#"char s[] = "abc"; int a[][2] = {{1, 2}, {3}, {4}}; int f(int p[], char *q[]);"#);
+    let types: Vec<&Type> = program.declarations[..2]
+        .iter()
+        .map(|decl| match decl {
+            Declaration::Variable(decl) => &decl.var_type,
+            Declaration::Function(_) => panic!("expected a variable declaration"),
+        })
+        .collect();
+    let pair = Type::Array(Box::new(Type::Int), 2);
+    assert_eq!(
+        types,
+        vec![
+            &Type::Array(Box::new(Type::Char), 4),
+            &Type::Array(Box::new(pair), 3),
+        ]
+    );
+    // Parameters are pointers whatever their size
+    let char_pointer = Type::Pointer(Box::new(Type::Char));
+    assert_eq!(
+        functions(&program)[0].fun_type,
+        Type::Function {
+            params: vec![
+                Type::Pointer(Box::new(Type::Int)),
+                Type::Pointer(Box::new(char_pointer)),
+            ],
+            ret: Box::new(Type::Int),
+        }
+    );
+}
+
+#[test]
+fn test_constant_expression_array_sizes() {
+    let program =
+        parse("int a[2 * 3]; long b[(1 << 2) + 1][10 / 3]; char c[1 ? 4u : 2l]; int d[(int) 2.9];");
+    let types: Vec<String> = program
+        .declarations
+        .iter()
+        .map(|decl| match decl {
+            Declaration::Variable(decl) => decl.var_type.to_string(),
+            Declaration::Function(_) => panic!("expected a variable declaration"),
+        })
+        .collect();
+    assert_eq!(types, ["int[6]", "long[5][3]", "char[4]", "int[2]"]);
+}
+
+#[test]
+fn test_extern_array_without_size() {
+    let program = parse("extern int a[]; extern long b[][3]; int main(void) { extern char c[]; }");
+    let Declaration::Variable(a) = &program.declarations[0] else {
+        panic!("expected a variable declaration");
+    };
+    assert_eq!(a.var_type, Type::Array(Box::new(Type::Int), 0));
+    assert_eq!(a.var_type.to_string(), "int[]");
+    let Declaration::Variable(b) = &program.declarations[1] else {
+        panic!("expected a variable declaration");
+    };
+    assert_eq!(b.var_type.to_string(), "long[][3]");
+}
+
+#[test]
+fn test_invalid_array_declarators() {
+    let cases = [
+        ("int a[0];", "array size must be greater than zero"),
+        ("int a[2 - 3];", "array size must be greater than zero"),
+        ("int a[0u];", "array size must be greater than zero"),
+        ("int a[n];", "array size is not an integer constant"),
+        ("int a[1.5];", "array size is not an integer constant"),
+        ("int a[1 / 0];", "array size is not an integer constant"),
+        ("long a[1l << 61];", "array is too large"),
+        ("int a[1l << 40][1l << 30];", "array is too large"),
+        ("char a[18446744073709551615ul];", "array is too large"),
+        (
+            "int main(void) { return (long (*)[1l << 61]) 0; }",
+            "array is too large",
+        ),
+        ("int a[];", "array size missing"),
+        ("static int a[];", "array size missing"),
+        ("extern int a[2][];", "array size missing"),
+        ("int a[2][] = {{1}};", "array size missing"),
+        ("int (*a)[] = 0;", "array size missing"),
+        ("int a[] = 1;", "array size missing"),
+        ("int main(void) { return (int[]) 0; }", "array size missing"),
+        ("int (f(void))[3];", "functions cannot return arrays"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(parse_error(source).to_string(), expected, "in {}", source);
+    }
+}
@@ -34,13 +34,44 @@ impl Scanner {
             ')' => self.make_token(TokenType::RightParen),
             '{' => self.make_token(TokenType::LeftBrace),
             '}' => self.make_token(TokenType::RightBrace),
+            '[' => self.make_token(TokenType::LeftBracket),
+            ']' => self.make_token(TokenType::RightBracket),
             ';' => self.make_token(TokenType::Semicolon),
+            '?' => self.make_token(TokenType::Question),
+            ':' => self.make_token(TokenType::Colon),
             ',' => self.make_token(TokenType::Comma),
+            '.' if self.peek().is_ascii_digit() => self.fraction(),
             '.' => self.make_token(TokenType::Dot),
-            '-' => self.make_token(TokenType::Minus),
+            '-' => {
+                let dec = self.matches('-');
+                self.make_token(if dec {
+                    TokenType::MinusMinus
+                } else {
+                    TokenType::Minus
+                })
+            }
             '+' => self.make_token(TokenType::Plus),
             '/' => self.make_token(TokenType::Slash),
             '*' => self.make_token(TokenType::Star),
+            '%' => self.make_token(TokenType::Percent),
+            '~' => self.make_token(TokenType::Tilde),
+            '&' => {
+                let and = self.matches('&');
+                self.make_token(if and {
+                    TokenType::AmpersandAmpersand
+                } else {
+                    TokenType::Ampersand
+                })
+            }
+            '|' => {
+                let or = self.matches('|');
+                self.make_token(if or {
+                    TokenType::PipePipe
+                } else {
+                    TokenType::Pipe
+                })
+            }
+            '^' => self.make_token(TokenType::Caret),
             '!' => {
                 let ne = self.matches('=');
                 self.make_token(if ne {
@@ -58,22 +89,27 @@ impl Scanner {
                 })
             }
             '<' => {
-                let lt = self.matches('=');
-                self.make_token(if lt {
+                let ttype = if self.matches('<') {
+                    TokenType::LessLess
+                } else if self.matches('=') {
                     TokenType::LessEqual
                 } else {
                     TokenType::Less
-                })
+                };
+                self.make_token(ttype)
             }
             '>' => {
-                let gt = self.matches('=');
-                self.make_token(if gt {
+                let ttype = if self.matches('>') {
+                    TokenType::GreaterGreater
+                } else if self.matches('=') {
                     TokenType::GreaterEqual
                 } else {
                     TokenType::Greater
-                })
+                };
+                self.make_token(ttype)
             }
             '"' => self.string(),
+            '\'' => self.char_literal(),
             '0'..='9' => self.number(),
             _ if c.is_alphabetic() || c == '_' => self.identifier(),
             _ => self.error_token("Unexpected character", c),
@@ -112,7 +148,7 @@ impl Scanner {
                         '*' => {
                             // A multi-line comment goes until '*/'
                             self.advance(); // skip over '*'
-                            while !(self.peek() == '*' && self.peek_next() == '/')
+                            while (self.peek() != '*' || self.peek_next() != '/')
                                 && !self.is_at_end()
                             {
                                 self.advance();
@@ -137,18 +173,97 @@ impl Scanner {
         }
     }
 
+    /// Scan a string literal. Like that of a character constant, the
+    /// lexeme is the decoded value, with one char for each byte.
     fn string(&mut self) -> Token {
-        while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.line += 1;
+        let mut bytes = Vec::new();
+        while self.peek() != '"' {
+            if self.is_at_end() || self.peek() == '\n' {
+                return self.error_message("Unterminated string.");
+            }
+            if let Err(error) = self.character(&mut bytes) {
+                return error;
             }
-            self.advance();
         }
-        if self.is_at_end() {
-            return self.error_message("Unterminated string.");
+        self.advance();
+        let value = bytes.iter().map(|&byte| byte as char).collect();
+        Token::new(TokenType::StringLiteral, value, self.line)
+    }
+
+    /// Scan a character constant. The lexeme of the token is the decoded
+    /// value rather than the quoted source text.
+    fn char_literal(&mut self) -> Token {
+        let mut bytes = Vec::new();
+        while self.peek() != '\'' {
+            if self.is_at_end() || self.peek() == '\n' {
+                return self.error_message("Unterminated character constant");
+            }
+            if let Err(error) = self.character(&mut bytes) {
+                return error;
+            }
         }
         self.advance();
-        self.make_token(TokenType::StringLiteral)
+        match bytes.len() {
+            0 => self.error_message("Empty character constant"),
+            1 => Token::new(
+                TokenType::CharLiteral,
+                (bytes[0] as char).to_string(),
+                self.line,
+            ),
+            _ => self.error_message("Multi-character constants are not supported"),
+        }
+    }
+
+    /// Consume one source character of a character constant or string
+    /// literal and append the bytes it stands for, decoding escape
+    /// sequences. Characters outside ASCII contribute their UTF-8 bytes.
+    fn character(&mut self, bytes: &mut Vec<u8>) -> Result<(), Token> {
+        let c = self.advance();
+        if c != '\\' || self.is_at_end() {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            return Ok(());
+        }
+        let byte = match self.advance() {
+            'n' => b'\n',
+            't' => b'\t',
+            'r' => b'\r',
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'v' => 0x0b,
+            '\\' => b'\\',
+            '\'' => b'\'',
+            '"' => b'"',
+            '?' => b'?',
+            c @ '0'..='7' => {
+                let mut value = c.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.peek().to_digit(8) {
+                        Some(digit) => value = value * 8 + digit,
+                        None => break,
+                    }
+                    self.advance();
+                }
+                u8::try_from(value)
+                    .map_err(|_| self.error_message("Octal escape sequence out of range"))?
+            }
+            'x' => {
+                if !self.peek().is_ascii_hexdigit() {
+                    return Err(self.error_message("\\x used with no following hex digits"));
+                }
+                let mut value: u32 = 0;
+                while let Some(digit) = self.peek().to_digit(16) {
+                    value = value.saturating_mul(16).saturating_add(digit);
+                    self.advance();
+                }
+                u8::try_from(value)
+                    .map_err(|_| self.error_message("Hex escape sequence out of range"))?
+            }
+            c => return Err(self.error_message(&format!("Unknown escape sequence \\{}", c))),
+        };
+        bytes.push(byte);
+        Ok(())
     }
 
     fn _number(&mut self) -> Token {
@@ -170,19 +285,49 @@ impl Scanner {
         while self.peek().is_ascii_digit() {
             self.advance();
         }
-        // Look for fraction
-        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
-            // consume '.'
+        // A fraction or an exponent makes this a floating constant
+        if self.matches('.') || matches!(self.peek(), 'e' | 'E') {
+            return self.fraction();
+        }
+        // An integer suffix is 'u', 'l' or both, in either order
+        let unsigned = self.matches('u') || self.matches('U');
+        let long = self.matches('l') || self.matches('L');
+        let unsigned = unsigned || (long && (self.matches('u') || self.matches('U')));
+        let ttype = match (unsigned, long) {
+            (false, false) => TokenType::Integer,
+            (false, true) => TokenType::LongInteger,
+            (true, false) => TokenType::UnsignedInteger,
+            (true, true) => TokenType::UnsignedLongInteger,
+        };
+        let c = self.peek();
+        if c.is_ascii_alphabetic() {
+            return self.error_token("Unexpected character", c);
+        }
+        self.make_token(ttype)
+    }
+
+    /// Scan the rest of a floating constant after the decimal point, if
+    /// any: the fractional digits and an optional exponent
+    fn fraction(&mut self) -> Token {
+        while self.peek().is_ascii_digit() {
             self.advance();
+        }
+        if self.matches('e') || self.matches('E') {
+            if !self.matches('+') {
+                self.matches('-');
+            }
+            if !self.peek().is_ascii_digit() {
+                return self.error_message("Exponent has no digits");
+            }
             while self.peek().is_ascii_digit() {
                 self.advance();
             }
         }
         let c = self.peek();
-        if c.is_ascii_alphabetic() {
+        if c.is_ascii_alphabetic() || c == '.' {
             return self.error_token("Unexpected character", c);
         }
-        self.make_token(TokenType::Integer)
+        self.make_token(TokenType::FloatLiteral)
     }
 
     fn identifier(&mut self) -> Token {
@@ -200,25 +345,36 @@ impl Scanner {
 
     fn identifier_type(&mut self) -> TokenType {
         match self.source[self.start] {
-            'e' => return self.check_keyword(1, 3, "lse", TokenType::Else),
-            'i' => {
-                if self.current - self.start > 1 {
-                    match self.source[self.start + 1] {
-                        'n' => return self.check_keyword(2, 1, "t", TokenType::Int),
-                        'f' => return TokenType::If,
-                        _ => {}
-                    }
-                }
-            }
-            'f' => {
-                if self.current - self.start > 1 {
-                    match self.source[self.start + 1] {
-                        'o' => return self.check_keyword(2, 1, "r", TokenType::For),
-                        _ => {}
-                    }
-                }
-            }
+            'b' => return self.check_keyword(1, 4, "reak", TokenType::Break),
+            'c' if self.current - self.start > 1 => match self.source[self.start + 1] {
+                'h' => return self.check_keyword(2, 2, "ar", TokenType::Char),
+                'o' => return self.check_keyword(2, 6, "ntinue", TokenType::Continue),
+                _ => {}
+            },
+            'd' if self.current - self.start > 1 => match self.source[self.start + 1] {
+                'o' if self.current - self.start == 2 => return TokenType::Do,
+                'o' => return self.check_keyword(2, 4, "uble", TokenType::Double),
+                _ => {}
+            },
+            'e' if self.current - self.start > 1 => match self.source[self.start + 1] {
+                'l' => return self.check_keyword(2, 2, "se", TokenType::Else),
+                'x' => return self.check_keyword(2, 4, "tern", TokenType::Extern),
+                _ => {}
+            },
+            'l' => return self.check_keyword(1, 3, "ong", TokenType::Long),
+            'i' if self.current - self.start > 1 => match self.source[self.start + 1] {
+                'n' => return self.check_keyword(2, 1, "t", TokenType::Int),
+                'f' => return self.check_keyword(2, 0, "", TokenType::If),
+                _ => {}
+            },
+            'f' => return self.check_keyword(1, 2, "or", TokenType::For),
             'r' => return self.check_keyword(1, 5, "eturn", TokenType::Return),
+            's' if self.current - self.start > 1 => match self.source[self.start + 1] {
+                'i' => return self.check_keyword(2, 4, "gned", TokenType::Signed),
+                't' => return self.check_keyword(2, 4, "atic", TokenType::Static),
+                _ => {}
+            },
+            'u' => return self.check_keyword(1, 7, "nsigned", TokenType::Unsigned),
             'w' => return self.check_keyword(1, 4, "hile", TokenType::While),
             'v' => return self.check_keyword(1, 3, "oid", TokenType::Void),
             _ => {}
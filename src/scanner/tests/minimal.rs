@@ -202,3 +202,202 @@ fn test_invalid_identifier_2() {
     ];
     run_scanner_tests(input, tests);
 }
+
+#[test]
+fn test_keyword_prefixes() {
+    let input = "if ifx f for fort int in /* a * b **/ return";
+    let tests = vec![
+        ExpectedToken(TokenType::If, "if", 1),
+        ExpectedToken(TokenType::Identifier, "ifx", 1),
+        ExpectedToken(TokenType::Identifier, "f", 1),
+        ExpectedToken(TokenType::For, "for", 1),
+        ExpectedToken(TokenType::Identifier, "fort", 1),
+        ExpectedToken(TokenType::Int, "int", 1),
+        ExpectedToken(TokenType::Identifier, "in", 1),
+        ExpectedToken(TokenType::Return, "return", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_keywords() {
+    let input =
+        "int void return if else while do for break continue static extern long signed unsigned double";
+    let tests = vec![
+        ExpectedToken(TokenType::Int, "int", 1),
+        ExpectedToken(TokenType::Void, "void", 1),
+        ExpectedToken(TokenType::Return, "return", 1),
+        ExpectedToken(TokenType::If, "if", 1),
+        ExpectedToken(TokenType::Else, "else", 1),
+        ExpectedToken(TokenType::While, "while", 1),
+        ExpectedToken(TokenType::Do, "do", 1),
+        ExpectedToken(TokenType::For, "for", 1),
+        ExpectedToken(TokenType::Break, "break", 1),
+        ExpectedToken(TokenType::Continue, "continue", 1),
+        ExpectedToken(TokenType::Static, "static", 1),
+        ExpectedToken(TokenType::Extern, "extern", 1),
+        ExpectedToken(TokenType::Long, "long", 1),
+        ExpectedToken(TokenType::Signed, "signed", 1),
+        ExpectedToken(TokenType::Unsigned, "unsigned", 1),
+        ExpectedToken(TokenType::Double, "double", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_keyword_prefixes_are_identifiers() {
+    let input = "iff doo d breaks continu format e ex statics s sign u doubles dou";
+    let tests = vec![
+        ExpectedToken(TokenType::Identifier, "iff", 1),
+        ExpectedToken(TokenType::Identifier, "doo", 1),
+        ExpectedToken(TokenType::Identifier, "d", 1),
+        ExpectedToken(TokenType::Identifier, "breaks", 1),
+        ExpectedToken(TokenType::Identifier, "continu", 1),
+        ExpectedToken(TokenType::Identifier, "format", 1),
+        ExpectedToken(TokenType::Identifier, "e", 1),
+        ExpectedToken(TokenType::Identifier, "ex", 1),
+        ExpectedToken(TokenType::Identifier, "statics", 1),
+        ExpectedToken(TokenType::Identifier, "s", 1),
+        ExpectedToken(TokenType::Identifier, "sign", 1),
+        ExpectedToken(TokenType::Identifier, "u", 1),
+        ExpectedToken(TokenType::Identifier, "doubles", 1),
+        ExpectedToken(TokenType::Identifier, "dou", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_long_constants() {
+    let input = "100l 200L 3 5ll";
+    let tests = vec![
+        ExpectedToken(TokenType::LongInteger, "100l", 1),
+        ExpectedToken(TokenType::LongInteger, "200L", 1),
+        ExpectedToken(TokenType::Integer, "3", 1),
+        ExpectedToken(TokenType::Error, "Unexpected character l", 1),
+        ExpectedToken(TokenType::Identifier, "l", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_unsigned_constants() {
+    let input = "1u 2U 3ul 4LU 5uL 6uu 7lul";
+    let tests = vec![
+        ExpectedToken(TokenType::UnsignedInteger, "1u", 1),
+        ExpectedToken(TokenType::UnsignedInteger, "2U", 1),
+        ExpectedToken(TokenType::UnsignedLongInteger, "3ul", 1),
+        ExpectedToken(TokenType::UnsignedLongInteger, "4LU", 1),
+        ExpectedToken(TokenType::UnsignedLongInteger, "5uL", 1),
+        ExpectedToken(TokenType::Error, "Unexpected character u", 1),
+        ExpectedToken(TokenType::Identifier, "u", 1),
+        ExpectedToken(TokenType::Error, "Unexpected character l", 1),
+        ExpectedToken(TokenType::Identifier, "l", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_floating_constants() {
+    let input = "1.5 .25 3. 1e10 2.5E-3 7e+2 1.0.";
+    let tests = vec![
+        ExpectedToken(TokenType::FloatLiteral, "1.5", 1),
+        ExpectedToken(TokenType::FloatLiteral, ".25", 1),
+        ExpectedToken(TokenType::FloatLiteral, "3.", 1),
+        ExpectedToken(TokenType::FloatLiteral, "1e10", 1),
+        ExpectedToken(TokenType::FloatLiteral, "2.5E-3", 1),
+        ExpectedToken(TokenType::FloatLiteral, "7e+2", 1),
+        ExpectedToken(TokenType::Error, "Unexpected character .", 1),
+        ExpectedToken(TokenType::Dot, ".", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_malformed_floating_constants() {
+    let input = "1e 2.5x";
+    let tests = vec![
+        ExpectedToken(TokenType::Error, "Exponent has no digits", 1),
+        ExpectedToken(TokenType::Error, "Unexpected character x", 1),
+        ExpectedToken(TokenType::Identifier, "x", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_char_literals() {
+    let input = r#"char c = 'a'; '\n' '\t' '\\' '\'' '\0' '\101' '\x41' '\x7f' '\377' '\?' '"'"#;
+    let tests = vec![
+        ExpectedToken(TokenType::Char, "char", 1),
+        ExpectedToken(TokenType::Identifier, "c", 1),
+        ExpectedToken(TokenType::Equal, "=", 1),
+        ExpectedToken(TokenType::CharLiteral, "a", 1),
+        ExpectedToken(TokenType::Semicolon, ";", 1),
+        ExpectedToken(TokenType::CharLiteral, "\n", 1),
+        ExpectedToken(TokenType::CharLiteral, "\t", 1),
+        ExpectedToken(TokenType::CharLiteral, "\\", 1),
+        ExpectedToken(TokenType::CharLiteral, "'", 1),
+        ExpectedToken(TokenType::CharLiteral, "\0", 1),
+        ExpectedToken(TokenType::CharLiteral, "A", 1),
+        ExpectedToken(TokenType::CharLiteral, "A", 1),
+        ExpectedToken(TokenType::CharLiteral, "\x7f", 1),
+        ExpectedToken(TokenType::CharLiteral, "\u{ff}", 1),
+        ExpectedToken(TokenType::CharLiteral, "?", 1),
+        ExpectedToken(TokenType::CharLiteral, "\"", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_malformed_char_literals() {
+    let cases = [
+        ("''", "Empty character constant"),
+        ("'ab'", "Multi-character constants are not supported"),
+        ("'a", "Unterminated character constant"),
+        ("'\n'", "Unterminated character constant"),
+        (r"'\q'", r"Unknown escape sequence \q"),
+        (r"'\400'", "Octal escape sequence out of range"),
+        (r"'\x100'", "Hex escape sequence out of range"),
+        (r"'\xg'", r"\x used with no following hex digits"),
+    ];
+    for (input, message) in cases {
+        let token = Scanner::new(input).scan_token();
+        assert_eq!(token.ttype, TokenType::Error, "in {}", input);
+        assert_eq!(token.lexeme, message, "in {}", input);
+    }
+}
+
+#[test]
+fn test_string_literals() {
+    let input = r#""hello" "a\tb\x41\101\"\\" "" "é""#;
+    let tests = vec![
+        ExpectedToken(TokenType::StringLiteral, "hello", 1),
+        ExpectedToken(TokenType::StringLiteral, "a\tbAA\"\\", 1),
+        ExpectedToken(TokenType::StringLiteral, "", 1),
+        // Other characters stand for their UTF-8 bytes
+        ExpectedToken(TokenType::StringLiteral, "\u{c3}\u{a9}", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_malformed_string_literals() {
+    // A string cannot span lines, so scanning picks up after the newline
+    let input = "\"one\ntwo\"";
+    let tests = vec![
+        ExpectedToken(TokenType::Error, "Unterminated string.", 1),
+        ExpectedToken(TokenType::Identifier, "two", 2),
+        ExpectedToken(TokenType::Error, "Unterminated string.", 2),
+    ];
+    run_scanner_tests(input, tests);
+    let token = Scanner::new(r#""\x""#).scan_token();
+    assert_eq!(token.lexeme, r"\x used with no following hex digits");
+}
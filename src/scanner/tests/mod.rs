@@ -1,6 +1,7 @@
 use super::*;
 
 mod minimal;
+mod operators;
 
 #[cfg(test)]
 struct ExpectedToken<'a>(TokenType, &'a str, usize);
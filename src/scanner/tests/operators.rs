@@ -0,0 +1,134 @@
+use super::*;
+
+#[test]
+fn test_unary_operators() {
+    let input = "return -~!5;";
+    let tests = vec![
+        ExpectedToken(TokenType::Return, "return", 1),
+        ExpectedToken(TokenType::Minus, "-", 1),
+        ExpectedToken(TokenType::Tilde, "~", 1),
+        ExpectedToken(TokenType::Bang, "!", 1),
+        ExpectedToken(TokenType::Integer, "5", 1),
+        ExpectedToken(TokenType::Semicolon, ";", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_decrement() {
+    let input = "--x - -y ---z";
+    let tests = vec![
+        ExpectedToken(TokenType::MinusMinus, "--", 1),
+        ExpectedToken(TokenType::Identifier, "x", 1),
+        ExpectedToken(TokenType::Minus, "-", 1),
+        ExpectedToken(TokenType::Minus, "-", 1),
+        ExpectedToken(TokenType::Identifier, "y", 1),
+        ExpectedToken(TokenType::MinusMinus, "--", 1),
+        ExpectedToken(TokenType::Minus, "-", 1),
+        ExpectedToken(TokenType::Identifier, "z", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_arithmetic_operators() {
+    let input = "1 + 2 - 3 * 4 / 5 % 6";
+    let tests = vec![
+        ExpectedToken(TokenType::Integer, "1", 1),
+        ExpectedToken(TokenType::Plus, "+", 1),
+        ExpectedToken(TokenType::Integer, "2", 1),
+        ExpectedToken(TokenType::Minus, "-", 1),
+        ExpectedToken(TokenType::Integer, "3", 1),
+        ExpectedToken(TokenType::Star, "*", 1),
+        ExpectedToken(TokenType::Integer, "4", 1),
+        ExpectedToken(TokenType::Slash, "/", 1),
+        ExpectedToken(TokenType::Integer, "5", 1),
+        ExpectedToken(TokenType::Percent, "%", 1),
+        ExpectedToken(TokenType::Integer, "6", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_bitwise_operators() {
+    let input = "a & b | c ^ d << e >> f < g <= h > i >= j";
+    let tests = vec![
+        ExpectedToken(TokenType::Identifier, "a", 1),
+        ExpectedToken(TokenType::Ampersand, "&", 1),
+        ExpectedToken(TokenType::Identifier, "b", 1),
+        ExpectedToken(TokenType::Pipe, "|", 1),
+        ExpectedToken(TokenType::Identifier, "c", 1),
+        ExpectedToken(TokenType::Caret, "^", 1),
+        ExpectedToken(TokenType::Identifier, "d", 1),
+        ExpectedToken(TokenType::LessLess, "<<", 1),
+        ExpectedToken(TokenType::Identifier, "e", 1),
+        ExpectedToken(TokenType::GreaterGreater, ">>", 1),
+        ExpectedToken(TokenType::Identifier, "f", 1),
+        ExpectedToken(TokenType::Less, "<", 1),
+        ExpectedToken(TokenType::Identifier, "g", 1),
+        ExpectedToken(TokenType::LessEqual, "<=", 1),
+        ExpectedToken(TokenType::Identifier, "h", 1),
+        ExpectedToken(TokenType::Greater, ">", 1),
+        ExpectedToken(TokenType::Identifier, "i", 1),
+        ExpectedToken(TokenType::GreaterEqual, ">=", 1),
+        ExpectedToken(TokenType::Identifier, "j", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_logical_operators() {
+    let input = "a && b || c & d | e == f != !g";
+    let tests = vec![
+        ExpectedToken(TokenType::Identifier, "a", 1),
+        ExpectedToken(TokenType::AmpersandAmpersand, "&&", 1),
+        ExpectedToken(TokenType::Identifier, "b", 1),
+        ExpectedToken(TokenType::PipePipe, "||", 1),
+        ExpectedToken(TokenType::Identifier, "c", 1),
+        ExpectedToken(TokenType::Ampersand, "&", 1),
+        ExpectedToken(TokenType::Identifier, "d", 1),
+        ExpectedToken(TokenType::Pipe, "|", 1),
+        ExpectedToken(TokenType::Identifier, "e", 1),
+        ExpectedToken(TokenType::EqualEqual, "==", 1),
+        ExpectedToken(TokenType::Identifier, "f", 1),
+        ExpectedToken(TokenType::BangEqual, "!=", 1),
+        ExpectedToken(TokenType::Bang, "!", 1),
+        ExpectedToken(TokenType::Identifier, "g", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_conditional_operator() {
+    let input = "a ? b : c";
+    let tests = vec![
+        ExpectedToken(TokenType::Identifier, "a", 1),
+        ExpectedToken(TokenType::Question, "?", 1),
+        ExpectedToken(TokenType::Identifier, "b", 1),
+        ExpectedToken(TokenType::Colon, ":", 1),
+        ExpectedToken(TokenType::Identifier, "c", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
+
+#[test]
+fn test_brackets() {
+    let input = "a[1][n]";
+    let tests = vec![
+        ExpectedToken(TokenType::Identifier, "a", 1),
+        ExpectedToken(TokenType::LeftBracket, "[", 1),
+        ExpectedToken(TokenType::Integer, "1", 1),
+        ExpectedToken(TokenType::RightBracket, "]", 1),
+        ExpectedToken(TokenType::LeftBracket, "[", 1),
+        ExpectedToken(TokenType::Identifier, "n", 1),
+        ExpectedToken(TokenType::RightBracket, "]", 1),
+        ExpectedToken(TokenType::Eof, "", 1),
+    ];
+    run_scanner_tests(input, tests);
+}
@@ -24,15 +24,27 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
     Dot,
     Minus,
     Plus,
     Semicolon,
+    Question,
+    Colon,
     Slash,
     Star,
+    Percent,
+    Tilde,
+    Ampersand,
+    Pipe,
+    Caret,
 
     // One or two character tokens.
+    MinusMinus,
+    AmpersandAmpersand,
+    PipePipe,
     Bang,
     BangEqual,
     Equal,
@@ -41,19 +53,36 @@ pub enum TokenType {
     GreaterEqual,
     Less,
     LessEqual,
+    LessLess,
+    GreaterGreater,
 
     // Literals.
     Identifier,
     StringLiteral,
+    CharLiteral,
     Integer,
+    LongInteger,
+    UnsignedInteger,
+    UnsignedLongInteger,
+    FloatLiteral,
 
     // Keywords.
     Void,
+    Char,
     Int,
+    Long,
+    Signed,
+    Unsigned,
+    Double,
+    Break,
+    Continue,
+    Do,
     Else,
+    Extern,
     For,
     If,
     Return,
+    Static,
     While,
     Error,
     Eof,
@@ -66,13 +95,25 @@ impl From<TokenType> for &'static str {
             TokenType::RightParen => ")",
             TokenType::LeftBrace => "{",
             TokenType::RightBrace => "}",
+            TokenType::LeftBracket => "[",
+            TokenType::RightBracket => "]",
             TokenType::Comma => ",",
             TokenType::Dot => ".",
             TokenType::Minus => "-",
             TokenType::Plus => "+",
             TokenType::Semicolon => ";",
+            TokenType::Question => "?",
+            TokenType::Colon => ":",
             TokenType::Slash => "/",
             TokenType::Star => "*",
+            TokenType::Percent => "%",
+            TokenType::Tilde => "~",
+            TokenType::Ampersand => "&",
+            TokenType::Pipe => "|",
+            TokenType::Caret => "^",
+            TokenType::MinusMinus => "--",
+            TokenType::AmpersandAmpersand => "&&",
+            TokenType::PipePipe => "||",
             TokenType::Bang => "!",
             TokenType::BangEqual => "!=",
             TokenType::Equal => "=",
@@ -81,15 +122,32 @@ impl From<TokenType> for &'static str {
             TokenType::GreaterEqual => ">=",
             TokenType::Less => "<",
             TokenType::LessEqual => "<=",
+            TokenType::LessLess => "<<",
+            TokenType::GreaterGreater => ">>",
             TokenType::Identifier => "identifier",
             TokenType::StringLiteral => "string",
+            TokenType::CharLiteral => "character constant",
             TokenType::Integer => "integer",
+            TokenType::LongInteger => "long integer",
+            TokenType::UnsignedInteger => "unsigned integer",
+            TokenType::UnsignedLongInteger => "unsigned long integer",
+            TokenType::FloatLiteral => "floating constant",
             TokenType::Void => "void",
+            TokenType::Char => "char",
             TokenType::Int => "int",
+            TokenType::Long => "long",
+            TokenType::Signed => "signed",
+            TokenType::Unsigned => "unsigned",
+            TokenType::Double => "double",
+            TokenType::Break => "break",
+            TokenType::Continue => "continue",
+            TokenType::Do => "do",
             TokenType::Else => "else",
+            TokenType::Extern => "extern",
             TokenType::For => "for",
             TokenType::If => "if",
             TokenType::Return => "return",
+            TokenType::Static => "static",
             TokenType::While => "while",
             TokenType::Error => "error",
             TokenType::Eof => "eof",
@@ -0,0 +1,149 @@
+//! Evaluation of constant expressions, which the parser needs for
+//! array dimensions and the type checker for static initializers
+
+use std::cmp::Ordering;
+
+use crate::parser::ast::*;
+use crate::semantic::typecheck::{common_type, is_comparison, promoted};
+
+/// The value of a constant expression, computed with the conversions
+/// the generated code would apply at run time, or None when it cannot
+/// be known before the program starts
+pub fn constant_value(exp: &Expression) -> Option<Const> {
+    match &exp.kind {
+        ExpressionKind::Constant(value) => Some(*value),
+        ExpressionKind::Cast(ty, operand) if ty.is_arithmetic() => {
+            Some(constant_value(operand)?.convert_to(ty))
+        }
+        ExpressionKind::Unary(op, operand) => unary_value(*op, constant_value(operand)?),
+        ExpressionKind::Binary(op, left, right) => {
+            binary_value(*op, constant_value(left)?, constant_value(right)?)
+        }
+        ExpressionKind::Conditional(condition, then, otherwise) => {
+            let condition = constant_value(condition)?;
+            let (then, otherwise) = (constant_value(then)?, constant_value(otherwise)?);
+            let ty = common_type(&then.ty(), &otherwise.ty());
+            let value = if is_zero(condition) { otherwise } else { then };
+            Some(value.convert_to(&ty))
+        }
+        _ => None,
+    }
+}
+
+fn unary_value(op: UnaryOperator, value: Const) -> Option<Const> {
+    let ty = promoted(&value.ty());
+    let value = value.convert_to(&ty);
+    let result = match (op, value) {
+        (UnaryOperator::Not, _) => return Some(Const::Int(is_zero(value) as i32)),
+        (UnaryOperator::Negate, Const::Double(value)) => return Some(Const::Double(-value)),
+        (UnaryOperator::Complement, Const::Double(_)) => return None,
+        (UnaryOperator::Negate, _) => integer_bits(value).wrapping_neg(),
+        (UnaryOperator::Complement, _) => !integer_bits(value),
+    };
+    Some(Const::Long(result).convert_to(&ty))
+}
+
+fn binary_value(op: BinaryOperator, left: Const, right: Const) -> Option<Const> {
+    match op {
+        BinaryOperator::And => return Some(Const::Int((!is_zero(left) && !is_zero(right)) as i32)),
+        BinaryOperator::Or => return Some(Const::Int((!is_zero(left) || !is_zero(right)) as i32)),
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => {
+            return shift_value(op, left, right)
+        }
+        _ => {}
+    }
+    let ty = common_type(&left.ty(), &right.ty());
+    let (left, right) = (left.convert_to(&ty), right.convert_to(&ty));
+    if is_comparison(op) {
+        let ordering = match (left, right) {
+            (Const::Double(left), Const::Double(right)) => left.partial_cmp(&right),
+            _ if ty.is_signed() => Some(integer_bits(left).cmp(&integer_bits(right))),
+            _ => Some((integer_bits(left) as u64).cmp(&(integer_bits(right) as u64))),
+        };
+        return Some(Const::Int(comparison_holds(op, ordering) as i32));
+    }
+    if let (Const::Double(left), Const::Double(right)) = (left, right) {
+        return match op {
+            BinaryOperator::Add => Some(Const::Double(left + right)),
+            BinaryOperator::Subtract => Some(Const::Double(left - right)),
+            BinaryOperator::Multiply => Some(Const::Double(left * right)),
+            BinaryOperator::Divide => Some(Const::Double(left / right)),
+            _ => None,
+        };
+    }
+    let (left, right) = (integer_bits(left), integer_bits(right));
+    let value = match op {
+        BinaryOperator::Add => left.wrapping_add(right),
+        BinaryOperator::Subtract => left.wrapping_sub(right),
+        BinaryOperator::Multiply => left.wrapping_mul(right),
+        // Integer division by zero traps at run time
+        BinaryOperator::Divide | BinaryOperator::Remainder if right == 0 => return None,
+        BinaryOperator::Divide if ty.is_signed() => left.wrapping_div(right),
+        BinaryOperator::Divide => ((left as u64) / (right as u64)) as i64,
+        BinaryOperator::Remainder if ty.is_signed() => left.wrapping_rem(right),
+        BinaryOperator::Remainder => ((left as u64) % (right as u64)) as i64,
+        BinaryOperator::BitwiseAnd => left & right,
+        BinaryOperator::BitwiseOr => left | right,
+        BinaryOperator::BitwiseXor => left ^ right,
+        _ => unreachable!("handled above"),
+    };
+    Some(Const::Long(value).convert_to(&ty))
+}
+
+/// The operands of a shift are promoted but not converted to a common
+/// type: the result has the type of the left one, and the count is masked to its
+/// width as by the shift instructions
+fn shift_value(op: BinaryOperator, left: Const, right: Const) -> Option<Const> {
+    let ty = promoted(&left.ty());
+    if ty == Type::Double || right.ty() == Type::Double {
+        return None;
+    }
+    let count = integer_bits(right) & (ty.size() as i64 * 8 - 1);
+    let bits = integer_bits(left.convert_to(&ty));
+    let value = if op == BinaryOperator::ShiftLeft {
+        bits << count
+    } else if ty.is_signed() {
+        bits >> count
+    } else {
+        ((bits as u64) >> count) as i64
+    };
+    Some(Const::Long(value).convert_to(&ty))
+}
+
+/// Whether a comparison holds between operands ordered as given. NaN is
+/// unordered, so only != holds when either operand is NaN.
+fn comparison_holds(op: BinaryOperator, ordering: Option<Ordering>) -> bool {
+    match op {
+        BinaryOperator::Equal => ordering == Some(Ordering::Equal),
+        BinaryOperator::NotEqual => ordering != Some(Ordering::Equal),
+        BinaryOperator::LessThan => ordering == Some(Ordering::Less),
+        BinaryOperator::LessOrEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        BinaryOperator::GreaterThan => ordering == Some(Ordering::Greater),
+        BinaryOperator::GreaterOrEqual => {
+            matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+        }
+        _ => unreachable!("not a comparison"),
+    }
+}
+
+/// An integer constant widened to 64 bits. Only signed values are sign
+/// extended, so that unsigned ones keep their value when read back as
+/// u64.
+fn integer_bits(value: Const) -> i64 {
+    match value {
+        Const::Char(value) => value as i64,
+        Const::UChar(value) => value as i64,
+        Const::Int(value) => value as i64,
+        Const::Long(value) => value,
+        Const::UInt(value) => value as i64,
+        Const::ULong(value) => value as i64,
+        Const::Double(_) => unreachable!("doubles are not integers"),
+    }
+}
+
+pub fn is_zero(value: Const) -> bool {
+    match value {
+        Const::Double(value) => value == 0.0,
+        value => integer_bits(value) == 0,
+    }
+}
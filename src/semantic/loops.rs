@@ -0,0 +1,71 @@
+use crate::parser::ast::*;
+use crate::semantic::SemanticError;
+
+/// Give every loop a unique label and annotate each break and continue
+/// statement with the label of its innermost enclosing loop
+pub fn label_loops(program: &mut Program) -> Result<(), SemanticError> {
+    let mut labeler = LoopLabeler::default();
+    for decl in &mut program.declarations {
+        if let Declaration::Function(FunctionDeclaration {
+            body: Some(body), ..
+        }) = decl
+        {
+            labeler.block(body, None)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct LoopLabeler {
+    counter: usize,
+}
+
+impl LoopLabeler {
+    fn block(&mut self, block: &mut Block, current: Option<&str>) -> Result<(), SemanticError> {
+        for item in &mut block.0 {
+            if let BlockItem::Statement(stmt) = item {
+                self.statement(stmt, current)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn statement(
+        &mut self,
+        stmt: &mut Statement,
+        current: Option<&str>,
+    ) -> Result<(), SemanticError> {
+        match stmt {
+            Statement::Break { label, line } => match current {
+                Some(current) => *label = current.to_string(),
+                None => return Err(SemanticError::BreakOutsideLoop(*line)),
+            },
+            Statement::Continue { label, line } => match current {
+                Some(current) => *label = current.to_string(),
+                None => return Err(SemanticError::ContinueOutsideLoop(*line)),
+            },
+            Statement::While { body, label, .. }
+            | Statement::DoWhile { body, label, .. }
+            | Statement::For { body, label, .. } => {
+                *label = self.make_label();
+                self.statement(body, Some(label))?;
+            }
+            Statement::If(_, then, otherwise) => {
+                self.statement(then, current)?;
+                if let Some(otherwise) = otherwise {
+                    self.statement(otherwise, current)?;
+                }
+            }
+            Statement::Compound(block) => self.block(block, current)?,
+            Statement::Return(_) | Statement::Expression(_) | Statement::Null => {}
+        }
+        Ok(())
+    }
+
+    fn make_label(&mut self) -> String {
+        let label = format!("loop.{}", self.counter);
+        self.counter += 1;
+        label
+    }
+}
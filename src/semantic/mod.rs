@@ -0,0 +1,209 @@
+pub mod constant;
+mod loops;
+mod resolve;
+pub mod symbols;
+#[cfg(test)]
+mod tests;
+mod typecheck;
+
+use std::fmt;
+
+use crate::parser::ast::{Program, Type};
+use crate::semantic::symbols::SymbolTable;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SemanticError {
+    /// A name declared twice in the same scope
+    DuplicateDeclaration(String, usize),
+    /// A variable used without being declared
+    UndeclaredVariable(String, usize),
+    /// The left side of an assignment is not something that can be
+    /// assigned to
+    InvalidLvalue(usize),
+    /// The operand of '&' is not something that has an address
+    AddressOfRvalue(usize),
+    /// A call to a function that has not been declared
+    UndeclaredFunction(String, usize),
+    /// A function definition inside the body of another function
+    NestedFunctionDefinition(String, usize),
+    /// A function declared with different types
+    ConflictingDeclaration(String, usize),
+    /// A function or variable with more than one definition
+    Redefinition(String, usize),
+    /// Declarations of the same name with internal and external linkage
+    ConflictingLinkage(String, usize),
+    /// A variable with static storage duration initialized with
+    /// something other than a constant
+    NonConstantInitializer(String, usize),
+    /// A block scope extern declaration with an initializer
+    ExternWithInitializer(String, usize),
+    /// A storage class that is not allowed on this declaration
+    InvalidStorageClass(String, usize),
+    /// A function name used where a variable is expected
+    FunctionUsedAsVariable(String, usize),
+    /// A call to something that is not a function
+    VariableUsedAsFunction(String, usize),
+    /// A call with the wrong number of arguments
+    WrongArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        line: usize,
+    },
+    /// An operator applied to an operand of a type it does not accept
+    InvalidOperand(&'static str, Type, usize),
+    /// A value converted to a type it cannot implicitly convert to, or
+    /// operands whose types do not fit together
+    IncompatibleTypes(Type, Type, usize),
+    /// An explicit conversion between types that cannot be converted
+    InvalidCast(Type, Type, usize),
+    /// An assignment to a variable of array type
+    ArrayAssignment(usize),
+    /// A brace-enclosed list for a scalar, or a single expression for an
+    /// array
+    InvalidInitializer(Type, usize),
+    /// An initializer list with more elements than the array has
+    ExcessElements(Type, usize),
+    /// A string literal with more characters than the array it
+    /// initializes
+    StringTooLong(Type, usize),
+    /// A break statement that is not inside a loop
+    BreakOutsideLoop(usize),
+    /// A continue statement that is not inside a loop
+    ContinueOutsideLoop(usize),
+}
+
+impl SemanticError {
+    pub fn line(&self) -> usize {
+        match self {
+            SemanticError::DuplicateDeclaration(_, line)
+            | SemanticError::UndeclaredVariable(_, line)
+            | SemanticError::InvalidLvalue(line)
+            | SemanticError::AddressOfRvalue(line)
+            | SemanticError::UndeclaredFunction(_, line)
+            | SemanticError::NestedFunctionDefinition(_, line)
+            | SemanticError::ConflictingDeclaration(_, line)
+            | SemanticError::Redefinition(_, line)
+            | SemanticError::ConflictingLinkage(_, line)
+            | SemanticError::NonConstantInitializer(_, line)
+            | SemanticError::ExternWithInitializer(_, line)
+            | SemanticError::InvalidStorageClass(_, line)
+            | SemanticError::FunctionUsedAsVariable(_, line)
+            | SemanticError::VariableUsedAsFunction(_, line)
+            | SemanticError::WrongArgumentCount { line, .. }
+            | SemanticError::InvalidOperand(_, _, line)
+            | SemanticError::IncompatibleTypes(_, _, line)
+            | SemanticError::InvalidCast(_, _, line)
+            | SemanticError::ArrayAssignment(line)
+            | SemanticError::InvalidInitializer(_, line)
+            | SemanticError::ExcessElements(_, line)
+            | SemanticError::StringTooLong(_, line)
+            | SemanticError::BreakOutsideLoop(line)
+            | SemanticError::ContinueOutsideLoop(line) => *line,
+        }
+    }
+}
+
+impl fmt::Display for SemanticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SemanticError::DuplicateDeclaration(name, _) => {
+                write!(f, "redeclaration of '{}'", name)
+            }
+            SemanticError::UndeclaredVariable(name, _) => {
+                write!(f, "use of undeclared variable '{}'", name)
+            }
+            SemanticError::InvalidLvalue(_) => {
+                write!(f, "invalid lvalue on the left side of assignment")
+            }
+            SemanticError::AddressOfRvalue(_) => {
+                write!(f, "cannot take the address of an rvalue")
+            }
+            SemanticError::UndeclaredFunction(name, _) => {
+                write!(f, "call to undeclared function '{}'", name)
+            }
+            SemanticError::NestedFunctionDefinition(name, _) => {
+                write!(
+                    f,
+                    "function '{}' cannot be defined inside another function",
+                    name
+                )
+            }
+            SemanticError::ConflictingDeclaration(name, _) => {
+                write!(f, "conflicting declarations of '{}'", name)
+            }
+            SemanticError::Redefinition(name, _) => write!(f, "redefinition of '{}'", name),
+            SemanticError::ConflictingLinkage(name, _) => {
+                write!(f, "'{}' redeclared with different linkage", name)
+            }
+            SemanticError::NonConstantInitializer(name, _) => {
+                write!(
+                    f,
+                    "initializer for static variable '{}' is not constant",
+                    name
+                )
+            }
+            SemanticError::ExternWithInitializer(name, _) => {
+                write!(
+                    f,
+                    "block scope extern declaration of '{}' has an initializer",
+                    name
+                )
+            }
+            SemanticError::InvalidStorageClass(name, _) => {
+                write!(f, "invalid storage class for '{}'", name)
+            }
+            SemanticError::FunctionUsedAsVariable(name, _) => {
+                write!(f, "function '{}' used as a variable", name)
+            }
+            SemanticError::VariableUsedAsFunction(name, _) => {
+                write!(f, "called object '{}' is not a function", name)
+            }
+            SemanticError::WrongArgumentCount {
+                name,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "function '{}' takes {} argument(s) but {} were given",
+                name, expected, found
+            ),
+            SemanticError::InvalidOperand(op, ty, _) => {
+                write!(f, "invalid operand of type '{}' to '{}'", ty, op)
+            }
+            SemanticError::IncompatibleTypes(left, right, _) => {
+                write!(f, "incompatible types '{}' and '{}'", left, right)
+            }
+            SemanticError::InvalidCast(from, to, _) => {
+                write!(f, "cannot cast '{}' to '{}'", from, to)
+            }
+            SemanticError::ArrayAssignment(_) => {
+                write!(f, "cannot assign to an expression of array type")
+            }
+            SemanticError::InvalidInitializer(ty, _) => {
+                write!(f, "invalid initializer for type '{}'", ty)
+            }
+            SemanticError::ExcessElements(ty, _) => {
+                write!(f, "too many elements in initializer for '{}'", ty)
+            }
+            SemanticError::StringTooLong(ty, _) => {
+                write!(f, "initializer-string for '{}' is too long", ty)
+            }
+            SemanticError::BreakOutsideLoop(_) => write!(f, "break statement not within a loop"),
+            SemanticError::ContinueOutsideLoop(_) => {
+                write!(f, "continue statement not within a loop")
+            }
+        }
+    }
+}
+
+/// Check that the program is semantically valid and return its symbol
+/// table. Every local variable is renamed to a name that is unique
+/// across the whole program, and every loop gets a label shared with
+/// the break and continue statements that refer to it.
+pub fn analyze(program: &mut Program) -> Result<SymbolTable, SemanticError> {
+    resolve::resolve_program(program)?;
+    loops::label_loops(program)?;
+    typecheck::typecheck_program(program)
+}
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use crate::parser::ast::*;
+use crate::semantic::SemanticError;
+
+/// Resolve every identifier to its declaration, giving each variable
+/// without linkage a unique name. Functions and variables with linkage
+/// keep their names so that they can be linked with other translation
+/// units.
+pub fn resolve_program(program: &mut Program) -> Result<(), SemanticError> {
+    let mut resolver = Resolver::default();
+    resolver.scopes.push(HashMap::new());
+    program
+        .declarations
+        .iter_mut()
+        .try_for_each(|decl| match decl {
+            Declaration::Function(function) => resolver.function_declaration(function),
+            Declaration::Variable(decl) => resolver.file_scope_variable_declaration(decl),
+        })
+}
+
+/// The name a variable was declared with in the source, given its
+/// unique name
+pub fn source_name(unique_name: &str) -> &str {
+    unique_name.split('.').next().unwrap_or(unique_name)
+}
+
+struct Identifier {
+    unique_name: String,
+    /// Whether every declaration of the name refers to the same entity
+    has_linkage: bool,
+}
+
+#[derive(Default)]
+struct Resolver {
+    /// One map per nested scope from names in the source to the
+    /// identifiers they denote. The file scope is first and the
+    /// innermost block is last.
+    scopes: Vec<HashMap<String, Identifier>>,
+    counter: usize,
+}
+
+impl Resolver {
+    fn function_declaration(
+        &mut self,
+        function: &mut FunctionDeclaration,
+    ) -> Result<(), SemanticError> {
+        self.declare_with_linkage(&function.name, function.line)?;
+
+        // The parameters share a scope with the outermost block of the body
+        self.scopes.push(HashMap::new());
+        let result = self.function_scope(function);
+        self.scopes.pop();
+        result
+    }
+
+    fn function_scope(&mut self, function: &mut FunctionDeclaration) -> Result<(), SemanticError> {
+        for param in &mut function.params {
+            *param = self.declare_variable(param, function.line)?;
+        }
+        match &mut function.body {
+            Some(body) => self.block_items(body),
+            None => Ok(()),
+        }
+    }
+
+    fn block(&mut self, block: &mut Block) -> Result<(), SemanticError> {
+        self.scopes.push(HashMap::new());
+        let result = self.block_items(block);
+        self.scopes.pop();
+        result
+    }
+
+    fn block_items(&mut self, block: &mut Block) -> Result<(), SemanticError> {
+        block.0.iter_mut().try_for_each(|item| match item {
+            BlockItem::Statement(stmt) => self.statement(stmt),
+            BlockItem::Declaration(Declaration::Variable(decl)) => self.variable_declaration(decl),
+            BlockItem::Declaration(Declaration::Function(function)) => {
+                if function.storage_class == Some(StorageClass::Static) {
+                    return Err(SemanticError::InvalidStorageClass(
+                        function.name.clone(),
+                        function.line,
+                    ));
+                }
+                if function.body.is_some() {
+                    return Err(SemanticError::NestedFunctionDefinition(
+                        function.name.clone(),
+                        function.line,
+                    ));
+                }
+                self.function_declaration(function)
+            }
+        })
+    }
+
+    fn current_scope(&mut self) -> &mut HashMap<String, Identifier> {
+        self.scopes
+            .last_mut()
+            .expect("the file scope is always present")
+    }
+
+    /// Find the unique name of the innermost declaration of `name`
+    fn lookup(&self, name: &str) -> Option<&String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .map(|identifier| &identifier.unique_name)
+    }
+
+    /// Add a name with linkage to the current scope. It may be declared
+    /// more than once, but must not clash with a variable without linkage
+    /// in the same scope.
+    fn declare_with_linkage(&mut self, name: &str, line: usize) -> Result<(), SemanticError> {
+        if let Some(prev) = self.current_scope().get(name) {
+            if !prev.has_linkage {
+                return Err(SemanticError::DuplicateDeclaration(name.to_string(), line));
+            }
+        }
+        self.current_scope().insert(
+            name.to_string(),
+            Identifier {
+                unique_name: name.to_string(),
+                has_linkage: true,
+            },
+        );
+        Ok(())
+    }
+
+    /// Add a variable without linkage to the current scope and return its
+    /// unique name
+    fn declare_variable(&mut self, name: &str, line: usize) -> Result<String, SemanticError> {
+        if self.current_scope().contains_key(name) {
+            return Err(SemanticError::DuplicateDeclaration(name.to_string(), line));
+        }
+        let unique_name = self.make_unique(name);
+        self.current_scope().insert(
+            name.to_string(),
+            Identifier {
+                unique_name: unique_name.clone(),
+                has_linkage: false,
+            },
+        );
+        Ok(unique_name)
+    }
+
+    /// Every variable at file scope has linkage, so it keeps its name
+    fn file_scope_variable_declaration(
+        &mut self,
+        decl: &mut VariableDeclaration,
+    ) -> Result<(), SemanticError> {
+        self.declare_with_linkage(&decl.name, decl.line)?;
+        match &mut decl.init {
+            Some(init) => self.initializer(init),
+            None => Ok(()),
+        }
+    }
+
+    fn variable_declaration(
+        &mut self,
+        decl: &mut VariableDeclaration,
+    ) -> Result<(), SemanticError> {
+        // A local extern declaration refers to a variable defined elsewhere
+        if decl.storage_class == Some(StorageClass::Extern) {
+            self.declare_with_linkage(&decl.name, decl.line)?;
+        } else {
+            decl.name = self.declare_variable(&decl.name, decl.line)?;
+        }
+        // The variable is already in scope in its own initializer
+        if let Some(init) = &mut decl.init {
+            self.initializer(init)?;
+        }
+        Ok(())
+    }
+
+    fn initializer(&mut self, init: &mut Initializer) -> Result<(), SemanticError> {
+        match init {
+            Initializer::Single(exp) => self.expression(exp),
+            Initializer::Compound(items, _) => {
+                items.iter_mut().try_for_each(|item| self.initializer(item))
+            }
+        }
+    }
+
+    fn statement(&mut self, stmt: &mut Statement) -> Result<(), SemanticError> {
+        match stmt {
+            Statement::Return(exp) | Statement::Expression(exp) => self.expression(exp),
+            Statement::If(condition, then, otherwise) => {
+                self.expression(condition)?;
+                self.statement(then)?;
+                if let Some(otherwise) = otherwise {
+                    self.statement(otherwise)?;
+                }
+                Ok(())
+            }
+            Statement::Compound(block) => self.block(block),
+            Statement::While {
+                condition, body, ..
+            }
+            | Statement::DoWhile {
+                body, condition, ..
+            } => {
+                self.expression(condition)?;
+                self.statement(body)
+            }
+            Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                ..
+            } => {
+                // A declaration in the init clause is scoped to the loop
+                self.scopes.push(HashMap::new());
+                let result = self.for_loop(init, condition, post, body);
+                self.scopes.pop();
+                result
+            }
+            Statement::Break { .. } | Statement::Continue { .. } | Statement::Null => Ok(()),
+        }
+    }
+
+    fn for_loop(
+        &mut self,
+        init: &mut ForInit,
+        condition: &mut Option<Expression>,
+        post: &mut Option<Expression>,
+        body: &mut Statement,
+    ) -> Result<(), SemanticError> {
+        match init {
+            ForInit::Declaration(decl) if decl.storage_class.is_some() => {
+                return Err(SemanticError::InvalidStorageClass(
+                    decl.name.clone(),
+                    decl.line,
+                ));
+            }
+            ForInit::Declaration(decl) => self.variable_declaration(decl)?,
+            ForInit::Expression(Some(exp)) => self.expression(exp)?,
+            ForInit::Expression(None) => {}
+        }
+        if let Some(condition) = condition {
+            self.expression(condition)?;
+        }
+        if let Some(post) = post {
+            self.expression(post)?;
+        }
+        self.statement(body)
+    }
+
+    fn expression(&mut self, exp: &mut Expression) -> Result<(), SemanticError> {
+        match &mut exp.kind {
+            ExpressionKind::Constant(_) | ExpressionKind::String(_) => Ok(()),
+            ExpressionKind::Var(name) => match self.lookup(name) {
+                Some(unique_name) => {
+                    *name = unique_name.clone();
+                    Ok(())
+                }
+                None => Err(SemanticError::UndeclaredVariable(name.clone(), exp.line)),
+            },
+            ExpressionKind::Unary(_, operand)
+            | ExpressionKind::Cast(_, operand)
+            | ExpressionKind::Dereference(operand) => self.expression(operand),
+            ExpressionKind::AddressOf(operand) => {
+                if !is_lvalue(operand) {
+                    return Err(SemanticError::AddressOfRvalue(exp.line));
+                }
+                self.expression(operand)
+            }
+            ExpressionKind::Binary(_, left, right) | ExpressionKind::Subscript(left, right) => {
+                self.expression(left)?;
+                self.expression(right)
+            }
+            ExpressionKind::Assignment(left, right) => {
+                if !is_lvalue(left) {
+                    return Err(SemanticError::InvalidLvalue(exp.line));
+                }
+                self.expression(left)?;
+                self.expression(right)
+            }
+            ExpressionKind::Conditional(condition, then, otherwise) => {
+                self.expression(condition)?;
+                self.expression(then)?;
+                self.expression(otherwise)
+            }
+            ExpressionKind::FunctionCall(name, args) => {
+                match self.lookup(name) {
+                    Some(unique_name) => *name = unique_name.clone(),
+                    None => return Err(SemanticError::UndeclaredFunction(name.clone(), exp.line)),
+                }
+                args.iter_mut().try_for_each(|arg| self.expression(arg))
+            }
+        }
+    }
+
+    /// Give a local variable a name that is unique across the program.
+    /// The suffix starts with a '.', which cannot appear in a C
+    /// identifier.
+    fn make_unique(&mut self, name: &str) -> String {
+        let unique_name = format!("{}.{}", name, self.counter);
+        self.counter += 1;
+        unique_name
+    }
+}
+
+/// Whether an expression designates an object, which can be assigned to
+/// and have its address taken
+fn is_lvalue(exp: &Expression) -> bool {
+    matches!(
+        exp.kind,
+        ExpressionKind::Var(_)
+            | ExpressionKind::String(_)
+            | ExpressionKind::Dereference(_)
+            | ExpressionKind::Subscript(..)
+    )
+}
@@ -0,0 +1,169 @@
+//! The symbol table records the type and attributes of every function
+//! and variable in the program, keyed by its unique name
+
+use std::collections::HashMap;
+
+use crate::parser::ast::{Const, Type};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdentifierAttrs {
+    Function {
+        defined: bool,
+        global: bool,
+    },
+    /// A variable with static storage duration, which lives for the
+    /// whole run of the program
+    Static {
+        init: InitialValue,
+        global: bool,
+    },
+    /// A read-only object with a local label, such as a string literal
+    Constant(StaticInit),
+    Local,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitialValue {
+    /// A file scope declaration without an initializer, which becomes a
+    /// definition initialized to zero unless a later one has a value
+    Tentative,
+    /// The values laid out one after another from the start of the
+    /// variable. A scalar has a single one.
+    Initial(Vec<StaticInit>),
+    /// A declaration of a variable defined elsewhere
+    NoInitializer,
+}
+
+/// The initial value of a variable with static storage duration
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaticInit {
+    Char(i8),
+    UChar(u8),
+    Int(i32),
+    Long(i64),
+    UInt(u32),
+    ULong(u64),
+    Double(f64),
+    /// The given number of zero bytes
+    Zero(usize),
+    /// The bytes of a string literal, followed by a null byte unless the
+    /// array it initializes has no room for one
+    String {
+        bytes: Vec<u8>,
+        null_terminated: bool,
+    },
+    /// The address of a static object
+    Pointer(String),
+}
+
+impl StaticInit {
+    pub fn zero(ty: &Type) -> Self {
+        StaticInit::Zero(ty.size())
+    }
+
+    /// Convert a constant to the given type, as if by assignment
+    pub fn from_const(value: Const, ty: &Type) -> Self {
+        match value.convert_to(ty) {
+            Const::Char(value) => StaticInit::Char(value),
+            Const::UChar(value) => StaticInit::UChar(value),
+            Const::Int(value) => StaticInit::Int(value),
+            Const::Long(value) => StaticInit::Long(value),
+            Const::UInt(value) => StaticInit::UInt(value),
+            Const::ULong(value) => StaticInit::ULong(value),
+            Const::Double(value) => StaticInit::Double(value),
+        }
+    }
+
+    /// Whether every byte of the value is zero. Negative zero is not.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            StaticInit::Double(value) => value.to_bits() == 0,
+            init => matches!(
+                init,
+                StaticInit::Char(0)
+                    | StaticInit::UChar(0)
+                    | StaticInit::Int(0)
+                    | StaticInit::Long(0)
+                    | StaticInit::UInt(0)
+                    | StaticInit::ULong(0)
+                    | StaticInit::Zero(_)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub ty: Type,
+    pub attrs: IdentifierAttrs,
+}
+
+/// Symbols are kept in the order in which they were first declared so
+/// that the generated code does not depend on hashing
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    symbols: HashMap<String, Symbol>,
+    order: Vec<String>,
+    /// The number of string literals added so far
+    strings: usize,
+}
+
+impl SymbolTable {
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
+
+    pub fn insert(&mut self, name: &str, symbol: Symbol) {
+        if self.symbols.insert(name.to_string(), symbol).is_none() {
+            self.order.push(name.to_string());
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Symbol)> {
+        self.order
+            .iter()
+            .map(|name| (name.as_str(), &self.symbols[name]))
+    }
+
+    /// Whether `name` is a function with a definition in this
+    /// translation unit
+    pub fn is_defined_function(&self, name: &str) -> bool {
+        matches!(
+            self.get(name),
+            Some(Symbol {
+                attrs: IdentifierAttrs::Function { defined: true, .. },
+                ..
+            })
+        )
+    }
+
+    /// Whether `name` is an object with static storage duration: a
+    /// static variable or a constant
+    pub fn is_static(&self, name: &str) -> bool {
+        matches!(
+            self.get(name),
+            Some(Symbol {
+                attrs: IdentifierAttrs::Static { .. } | IdentifierAttrs::Constant(_),
+                ..
+            })
+        )
+    }
+
+    /// Add a string literal as a constant array of char with its
+    /// terminating null, and return its label
+    pub fn add_string(&mut self, bytes: &[u8]) -> String {
+        let name = format!(".Lstring.{}", self.strings);
+        self.strings += 1;
+        self.insert(
+            &name,
+            Symbol {
+                ty: Type::Array(Box::new(Type::Char), bytes.len() + 1),
+                attrs: IdentifierAttrs::Constant(StaticInit::String {
+                    bytes: bytes.to_vec(),
+                    null_terminated: true,
+                }),
+            },
+        );
+        name
+    }
+}
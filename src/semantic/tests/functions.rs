@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn test_parameters_are_renamed() {
+    let program = analyze_source("int f(int a, int b) { return a + b; }").unwrap();
+    assert_eq!(functions(&program)[0].params, vec!["a.0", "b.1"]);
+}
+
+#[test]
+fn test_function_names_are_kept() {
+    let input = "int f(void); int main(void) { int f(void); return f(); }";
+    let program = analyze_source(input).unwrap();
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[1] else {
+        panic!("expected a return statement");
+    };
+    assert_eq!(
+        exp.kind,
+        ExpressionKind::FunctionCall("f".to_string(), Vec::new())
+    );
+}
+
+#[test]
+fn test_duplicate_parameter() {
+    let e = semantic_error("int f(int a, int a) { return a; }");
+    assert_eq!(e, SemanticError::DuplicateDeclaration("a".to_string(), 1));
+}
+
+#[test]
+fn test_parameter_redeclared_in_body() {
+    let e = semantic_error("int f(int a) {\n int a = 1;\n return a;\n}");
+    assert_eq!(e, SemanticError::DuplicateDeclaration("a".to_string(), 2));
+}
+
+#[test]
+fn test_undeclared_function() {
+    let e = semantic_error("int main(void) {\n return g(1);\n}");
+    assert_eq!(e, SemanticError::UndeclaredFunction("g".to_string(), 2));
+    assert_eq!(e.to_string(), "call to undeclared function 'g'");
+}
+
+#[test]
+fn test_function_declaration_out_of_scope() {
+    let input = "int main(void) { { int g(void); } return g(); }";
+    assert_eq!(
+        semantic_error(input),
+        SemanticError::UndeclaredFunction("g".to_string(), 1)
+    );
+}
+
+#[test]
+fn test_nested_function_definition() {
+    let e = semantic_error("int main(void) {\n int f(void) { return 1; }\n return f();\n}");
+    assert_eq!(
+        e,
+        SemanticError::NestedFunctionDefinition("f".to_string(), 2)
+    );
+}
+
+#[test]
+fn test_function_declaration_clashes_with_local() {
+    let e = semantic_error("int main(void) { int f = 1; int f(void); return f; }");
+    assert_eq!(e, SemanticError::DuplicateDeclaration("f".to_string(), 1));
+}
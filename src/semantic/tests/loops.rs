@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn test_break_and_continue_refer_to_innermost_loop() {
+    let input = r#"int main(void) {
+        while (1) {
+            for (;;)
+                break;
+            continue;
+        }
+    }"#;
+    let program = analyze_source(input).unwrap();
+    let BlockItem::Statement(Statement::While { body, label, .. }) = &body(&program).0[0] else {
+        panic!("expected a while loop");
+    };
+    assert_eq!(label, "loop.0");
+    let Statement::Compound(block) = &**body else {
+        panic!("expected a compound body");
+    };
+    let BlockItem::Statement(Statement::For { body, label, .. }) = &block.0[0] else {
+        panic!("expected a for loop");
+    };
+    assert_eq!(label, "loop.1");
+    assert!(matches!(&**body, Statement::Break { label, .. } if label == "loop.1"));
+    assert!(matches!(
+        &block.0[1],
+        BlockItem::Statement(Statement::Continue { label, .. }) if label == "loop.0"
+    ));
+}
+
+#[test]
+fn test_break_outside_loop() {
+    let e = semantic_error("int main(void) {\n if (1) break;\n}");
+    assert_eq!(e, SemanticError::BreakOutsideLoop(2));
+    assert_eq!(e.to_string(), "break statement not within a loop");
+}
+
+#[test]
+fn test_continue_outside_loop() {
+    let e = semantic_error("int main(void) { { continue; } }");
+    assert_eq!(e, SemanticError::ContinueOutsideLoop(1));
+}
+
+#[test]
+fn test_for_declaration_is_scoped_to_loop() {
+    let e = semantic_error("int main(void) { for (int i = 0; i < 3; i = i + 1) ; return i; }");
+    assert_eq!(e, SemanticError::UndeclaredVariable("i".to_string(), 1));
+}
+
+#[test]
+fn test_for_declaration_may_shadow() {
+    let input = "int main(void) { int i = 5; for (int i = 0; i < 3; i = i + 1) ; return i; }";
+    assert!(analyze_source(input).is_ok());
+}
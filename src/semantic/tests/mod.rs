@@ -0,0 +1,48 @@
+use super::*;
+use crate::parser::ast::*;
+use crate::parser::Parser;
+use crate::semantic::symbols::*;
+
+mod functions;
+mod loops;
+mod scopes;
+mod storage;
+mod types;
+mod variables;
+
+#[cfg(test)]
+fn analyze_source(input: &str) -> Result<Program, SemanticError> {
+    let mut program = Parser::new(input).parse_program().expect("parse failed");
+    analyze(&mut program)?;
+    Ok(program)
+}
+
+#[cfg(test)]
+fn semantic_error(input: &str) -> SemanticError {
+    match analyze_source(input) {
+        Ok(program) => panic!("expected a semantic error, got {:?}", program),
+        Err(e) => e,
+    }
+}
+
+/// The body of the last function in the program
+#[cfg(test)]
+fn body(program: &Program) -> &Block {
+    functions(program)
+        .last()
+        .and_then(|function| function.body.as_ref())
+        .expect("expected a function definition")
+}
+
+/// The function declarations in the program, in source order
+#[cfg(test)]
+fn functions(program: &Program) -> Vec<&FunctionDeclaration> {
+    program
+        .declarations
+        .iter()
+        .filter_map(|decl| match decl {
+            Declaration::Function(function) => Some(function),
+            Declaration::Variable(_) => None,
+        })
+        .collect()
+}
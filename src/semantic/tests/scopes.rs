@@ -0,0 +1,88 @@
+use super::*;
+
+/// The unique names of the variables returned by each return statement,
+/// in source order
+#[cfg(test)]
+fn returned_names(block: &Block) -> Vec<String> {
+    let mut names = Vec::new();
+    for item in &block.0 {
+        match item {
+            BlockItem::Statement(Statement::Return(Expression {
+                kind: ExpressionKind::Var(name),
+                ..
+            })) => names.push(name.clone()),
+            BlockItem::Statement(Statement::Compound(inner)) => names.extend(returned_names(inner)),
+            _ => {}
+        }
+    }
+    names
+}
+
+#[test]
+fn test_shadowing() {
+    let input = r#"int main(void) {
+        int x = 1;
+        {
+            int x = 2;
+            return x;
+        }
+        return x;
+    }"#;
+    let program = analyze_source(input).unwrap();
+    assert_eq!(returned_names(body(&program)), vec!["x.1", "x.0"]);
+}
+
+#[test]
+fn test_outer_variable_visible_in_inner_block() {
+    let input = r#"int main(void) {
+        int x = 1;
+        {
+            {
+                return x;
+            }
+        }
+    }"#;
+    let program = analyze_source(input).unwrap();
+    assert_eq!(returned_names(body(&program)), vec!["x.0"]);
+}
+
+#[test]
+fn test_inner_variable_not_visible_after_block() {
+    let input = r#"int main(void) {
+        {
+            int y = 1;
+        }
+        return y;
+    }"#;
+    let err = semantic_error(input);
+    assert_eq!(err, SemanticError::UndeclaredVariable("y".to_string(), 5));
+}
+
+#[test]
+fn test_redeclaration_in_same_block() {
+    let input = r#"int main(void) {
+        int x = 1;
+        {
+            int x = 2;
+            int x = 3;
+        }
+        return x;
+    }"#;
+    let err = semantic_error(input);
+    assert_eq!(err, SemanticError::DuplicateDeclaration("x".to_string(), 5));
+}
+
+#[test]
+fn test_sibling_blocks() {
+    let input = r#"int main(void) {
+        {
+            int a = 1;
+        }
+        {
+            int a = 2;
+            return a;
+        }
+    }"#;
+    let program = analyze_source(input).unwrap();
+    assert_eq!(returned_names(body(&program)), vec!["a.1"]);
+}
@@ -0,0 +1,303 @@
+use super::*;
+
+#[cfg(test)]
+fn symbols_for(input: &str) -> SymbolTable {
+    let mut program = Parser::new(input).parse_program().expect("parse failed");
+    analyze(&mut program).expect("semantic analysis failed")
+}
+
+#[cfg(test)]
+fn static_attrs(symbols: &SymbolTable, name: &str) -> IdentifierAttrs {
+    symbols.get(name).expect("missing symbol").attrs.clone()
+}
+
+#[test]
+fn test_tentative_definitions() {
+    let symbols = symbols_for("int x; int x; int y; int y = 3; int y; extern int z;");
+    assert_eq!(
+        static_attrs(&symbols, "x"),
+        IdentifierAttrs::Static {
+            init: InitialValue::Tentative,
+            global: true,
+        }
+    );
+    assert_eq!(
+        static_attrs(&symbols, "y"),
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::Int(3)]),
+            global: true,
+        }
+    );
+    assert_eq!(
+        static_attrs(&symbols, "z"),
+        IdentifierAttrs::Static {
+            init: InitialValue::NoInitializer,
+            global: true,
+        }
+    );
+}
+
+#[test]
+fn test_extern_inherits_internal_linkage() {
+    let symbols = symbols_for("static int x = 2; extern int x; static int f(void); int f(void);");
+    assert_eq!(
+        static_attrs(&symbols, "x"),
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::Int(2)]),
+            global: false,
+        }
+    );
+    assert_eq!(
+        static_attrs(&symbols, "f"),
+        IdentifierAttrs::Function {
+            defined: false,
+            global: false,
+        }
+    );
+}
+
+#[test]
+fn test_static_local() {
+    let input = "int main(void) { static int count; extern int g; return count + g; }";
+    let program = analyze_source(input).unwrap();
+    let BlockItem::Declaration(Declaration::Variable(decl)) = &body(&program).0[0] else {
+        panic!("expected a declaration");
+    };
+    assert_eq!(decl.name, "count.0");
+    let symbols = symbols_for(input);
+    assert_eq!(
+        static_attrs(&symbols, "count.0"),
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::Zero(4)]),
+            global: false,
+        }
+    );
+    assert!(symbols.is_static("g"));
+}
+
+#[test]
+fn test_conflicting_linkage() {
+    let e = semantic_error("static int x;\nint x;");
+    assert_eq!(e, SemanticError::ConflictingLinkage("x".to_string(), 2));
+    assert_eq!(e.to_string(), "'x' redeclared with different linkage");
+    let e = semantic_error("int x;\nstatic int x;");
+    assert_eq!(e, SemanticError::ConflictingLinkage("x".to_string(), 2));
+    let e = semantic_error("int f(void);\nstatic int f(void);");
+    assert_eq!(e, SemanticError::ConflictingLinkage("f".to_string(), 2));
+}
+
+#[test]
+fn test_variable_redefinition() {
+    let e = semantic_error("int x = 1;\nint x = 2;");
+    assert_eq!(e, SemanticError::Redefinition("x".to_string(), 2));
+}
+
+#[test]
+fn test_function_and_variable_with_same_name() {
+    let e = semantic_error("int x;\nint x(void);");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("x".to_string(), 2));
+    let e = semantic_error("int f(void);\nint main(void) {\n extern int f;\n return 0;\n}");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("f".to_string(), 3));
+}
+
+#[test]
+fn test_non_constant_initializer() {
+    let e = semantic_error("int x = 1;\nint y = x;");
+    assert_eq!(e, SemanticError::NonConstantInitializer("y".to_string(), 2));
+    let e = semantic_error("int main(void) { int a = 1; static int b = a + 1; return b; }");
+    assert_eq!(
+        e.to_string(),
+        "initializer for static variable 'b' is not constant"
+    );
+    let e = semantic_error("int q = 1 / 0;");
+    assert_eq!(e, SemanticError::NonConstantInitializer("q".to_string(), 1));
+}
+
+#[test]
+fn test_constant_expression_initializers() {
+    let symbols = symbols_for(
+        "int x = -1; int y = 1 + 2 * 3; int z = 7 > 3 ? ~0 : 5; int s = -16 >> 2;\n\
+         int n = !0 && 5; int w = 2147483647 + 1;\n\
+         int main(void) { static int b = 10 % 4 - 7; return b; }",
+    );
+    let init = |name| match static_attrs(&symbols, name) {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init,
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("x"), vec![StaticInit::Int(-1)]);
+    assert_eq!(init("y"), vec![StaticInit::Int(7)]);
+    assert_eq!(init("z"), vec![StaticInit::Int(-1)]);
+    assert_eq!(init("s"), vec![StaticInit::Int(-4)]);
+    assert_eq!(init("n"), vec![StaticInit::Int(1)]);
+    // Overflow wraps around as it does at run time
+    assert_eq!(init("w"), vec![StaticInit::Int(i32::MIN)]);
+    assert_eq!(init("b.0"), vec![StaticInit::Int(-5)]);
+}
+
+#[test]
+fn test_constant_expressions_with_longs() {
+    let symbols = symbols_for(
+        "long l = (long)5; long m = 2147483647 + 1l; int t = (int)4294967297l;\n\
+         long big = -2147483648; long sh = 1l << 65; int i = 1 << 33; int c = 1 ? 7 : 2l;\n\
+         long q = 10000000000 / -3;",
+    );
+    let init = |name| match static_attrs(&symbols, name) {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init,
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("l"), vec![StaticInit::Long(5)]);
+    // The int operand is converted to long before the addition
+    assert_eq!(init("m"), vec![StaticInit::Long(2147483648)]);
+    assert_eq!(init("t"), vec![StaticInit::Int(1)]);
+    assert_eq!(init("big"), vec![StaticInit::Long(-2147483648)]);
+    assert_eq!(init("sh"), vec![StaticInit::Long(2)]);
+    assert_eq!(init("i"), vec![StaticInit::Int(2)]);
+    assert_eq!(init("c"), vec![StaticInit::Int(7)]);
+    assert_eq!(init("q"), vec![StaticInit::Long(-3333333333)]);
+}
+
+#[test]
+fn test_block_scope_extern_with_initializer() {
+    let e = semantic_error("int main(void) {\n extern int x = 1;\n return x;\n}");
+    assert_eq!(e, SemanticError::ExternWithInitializer("x".to_string(), 2));
+}
+
+#[test]
+fn test_local_and_extern_in_same_scope() {
+    let e = semantic_error("int main(void) { int x; extern int x; return x; }");
+    assert_eq!(e, SemanticError::DuplicateDeclaration("x".to_string(), 1));
+    let e = semantic_error("int main(void) { extern int x; int x; return x; }");
+    assert_eq!(e, SemanticError::DuplicateDeclaration("x".to_string(), 1));
+}
+
+#[test]
+fn test_invalid_storage_classes() {
+    let e = semantic_error("int main(void) {\n static int f(void);\n return 0;\n}");
+    assert_eq!(e, SemanticError::InvalidStorageClass("f".to_string(), 2));
+    let e = semantic_error("int main(void) { for (static int i = 0; i < 3; i = i + 1) ; }");
+    assert_eq!(e.to_string(), "invalid storage class for 'i'");
+}
+
+#[test]
+fn test_constant_expressions_with_unsigned_types() {
+    let symbols = symbols_for(
+        "unsigned u = ~0u; unsigned v = -1u / 2; int lt = -1 < 0u; unsigned long w = -1ul >> 60;\n\
+         long r = 4294967295u % 7; unsigned long d = 18446744073709551615ul / 3; int h = -1 >> 28;",
+    );
+    let init = |name| match static_attrs(&symbols, name) {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init,
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("u"), vec![StaticInit::UInt(u32::MAX)]);
+    assert_eq!(init("v"), vec![StaticInit::UInt(u32::MAX / 2)]);
+    // -1 is converted to unsigned before the comparison
+    assert_eq!(init("lt"), vec![StaticInit::Int(0)]);
+    assert_eq!(init("w"), vec![StaticInit::ULong(15)]);
+    assert_eq!(init("r"), vec![StaticInit::Long(3)]);
+    assert_eq!(init("d"), vec![StaticInit::ULong(u64::MAX / 3)]);
+    assert_eq!(init("h"), vec![StaticInit::Int(-1)]);
+}
+
+#[test]
+fn test_constant_expressions_with_doubles() {
+    let symbols = symbols_for(
+        "double d = -2.5; double z = -0.0; double h = 1 / 2.0; long t = (long)-2.9 * 2;\n\
+         int nan = 0.0 / 0.0 == 0.0 / 0.0; int ne = 0.0 / 0.0 != 0.0 / 0.0; int lt = -1.5 < 1u;\n\
+         unsigned long big = 1e19; double c = 0 ? 1 : 2.5;",
+    );
+    let init = |name| match static_attrs(&symbols, name) {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init,
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("d"), vec![StaticInit::Double(-2.5)]);
+    let [StaticInit::Double(zero)] = init("z")[..] else {
+        panic!("expected a double initializer");
+    };
+    assert_eq!(zero.to_bits(), (-0.0f64).to_bits());
+    assert!(!StaticInit::Double(zero).is_zero());
+    assert_eq!(init("h"), vec![StaticInit::Double(0.5)]);
+    assert_eq!(init("t"), vec![StaticInit::Long(-4)]);
+    // NaN compares unequal to everything, itself included
+    assert_eq!(init("nan"), vec![StaticInit::Int(0)]);
+    assert_eq!(init("ne"), vec![StaticInit::Int(1)]);
+    assert_eq!(init("lt"), vec![StaticInit::Int(1)]);
+    assert_eq!(
+        init("big"),
+        vec![StaticInit::ULong(10_000_000_000_000_000_000)]
+    );
+    assert_eq!(init("c"), vec![StaticInit::Double(2.5)]);
+    for source in [
+        "double x = ~1.0;",
+        "double x = 1.0 % 2;",
+        "int x = 1 << 2.0;",
+    ] {
+        assert!(matches!(
+            semantic_error(source),
+            SemanticError::NonConstantInitializer(..)
+        ));
+    }
+}
+
+#[test]
+fn test_static_array_constant_expressions() {
+    let symbols = symbols_for(
+        "int a[2] = {-1, 2 * 3}; long m[2][2] = {{~0, (long)-3}};\n\
+         int main(void) { static double d[3] = {-1.5}; return a[0]; }",
+    );
+    let init = |name| match static_attrs(&symbols, name) {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init,
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("a"), vec![StaticInit::Int(-1), StaticInit::Int(6)]);
+    assert_eq!(
+        init("m"),
+        vec![
+            StaticInit::Long(-1),
+            StaticInit::Long(-3),
+            StaticInit::Zero(16)
+        ]
+    );
+    assert_eq!(
+        init("d.0"),
+        vec![StaticInit::Double(-1.5), StaticInit::Zero(16)]
+    );
+    let e = semantic_error("int x = 1;\nint a[2] = {1, -x};");
+    assert_eq!(e, SemanticError::NonConstantInitializer("a".to_string(), 2));
+}
+
+#[test]
+fn test_constant_expressions_with_chars() {
+    let symbols = symbols_for(
+        "char c = -129; unsigned char u = ~(unsigned char)0; int n = -(unsigned char)255;\n\
+         int s = (char)-1 >> 1; int l = (unsigned char)1 << 9; int t = (char)200 < 0 ? 'a' : 'b';",
+    );
+    let init = |name| match static_attrs(&symbols, name) {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init,
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("c"), vec![StaticInit::Char(127)]);
+    // Characters are promoted to int before - and ~ apply
+    assert_eq!(init("u"), vec![StaticInit::UChar(255)]);
+    assert_eq!(init("n"), vec![StaticInit::Int(-255)]);
+    assert_eq!(init("s"), vec![StaticInit::Int(-1)]);
+    assert_eq!(init("l"), vec![StaticInit::Int(512)]);
+    assert_eq!(init("t"), vec![StaticInit::Int(97)]);
+}
@@ -0,0 +1,662 @@
+use super::*;
+
+#[test]
+fn test_symbol_table() {
+    let mut program = Parser::new("int f(int a); int main(void) { int x = 1; return f(x); }")
+        .parse_program()
+        .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    assert_eq!(
+        symbols.get("f"),
+        Some(&Symbol {
+            ty: Type::Function {
+                params: vec![Type::Int],
+                ret: Box::new(Type::Int),
+            },
+            attrs: IdentifierAttrs::Function {
+                defined: false,
+                global: true,
+            },
+        })
+    );
+    assert!(symbols.is_defined_function("main"));
+    assert!(!symbols.is_defined_function("f"));
+    assert_eq!(
+        symbols.get("x.1"),
+        Some(&Symbol {
+            ty: Type::Int,
+            attrs: IdentifierAttrs::Local,
+        })
+    );
+}
+
+#[test]
+fn test_declaration_after_definition() {
+    let input = "int f(void) { return 1; } int f(void); int main(void) { return f(); }";
+    let mut program = Parser::new(input).parse_program().unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    assert!(symbols.is_defined_function("f"));
+}
+
+#[test]
+fn test_wrong_argument_count() {
+    let e = semantic_error("int f(int a, int b);\nint main(void) {\n return f(1);\n}");
+    assert_eq!(
+        e,
+        SemanticError::WrongArgumentCount {
+            name: "f".to_string(),
+            expected: 2,
+            found: 1,
+            line: 3,
+        }
+    );
+    assert_eq!(
+        e.to_string(),
+        "function 'f' takes 2 argument(s) but 1 were given"
+    );
+}
+
+#[test]
+fn test_function_used_as_variable() {
+    let e = semantic_error("int f(void);\nint main(void) {\n return f + 1;\n}");
+    assert_eq!(e, SemanticError::FunctionUsedAsVariable("f".to_string(), 3));
+    let e = semantic_error("int f(void); int main(void) { f = 2; return 0; }");
+    assert_eq!(e.to_string(), "function 'f' used as a variable");
+}
+
+#[test]
+fn test_variable_used_as_function() {
+    let e = semantic_error("int main(void) {\n int x = 1;\n return x();\n}");
+    assert_eq!(e, SemanticError::VariableUsedAsFunction("x".to_string(), 3));
+    assert_eq!(e.to_string(), "called object 'x' is not a function");
+}
+
+#[test]
+fn test_conflicting_declarations() {
+    let e = semantic_error("int f(int a);\nint f(int a, int b);\nint main(void) { return 0; }");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("f".to_string(), 2));
+}
+
+#[test]
+fn test_conflicting_local_declaration() {
+    let e = semantic_error("int f(void);\nint main(void) {\n int f(int x);\n return 0;\n}");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("f".to_string(), 3));
+}
+
+#[test]
+fn test_multiple_definitions() {
+    let e = semantic_error("int f(void) { return 1; }\nint f(void) { return 2; }");
+    assert_eq!(e, SemanticError::Redefinition("f".to_string(), 2));
+    assert_eq!(e.to_string(), "redefinition of 'f'");
+}
+
+#[test]
+fn test_conflicting_types() {
+    let e = semantic_error("int f(int a);\nlong f(int a);");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("f".to_string(), 2));
+    let e = semantic_error("int f(int a);\nint f(long a);");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("f".to_string(), 2));
+    let e = semantic_error("long x;\nint main(void) {\n extern int x;\n return 0;\n}");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("x".to_string(), 3));
+    let e = semantic_error("int x;\nlong x;");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("x".to_string(), 2));
+}
+
+#[test]
+fn test_expressions_are_typed() {
+    let program = analyze_source("int main(void) { long l = 1; return l < 2; }").unwrap();
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[1] else {
+        panic!("expected a return statement");
+    };
+    assert_eq!(exp.ty(), &Type::Int);
+    let ExpressionKind::Binary(BinaryOperator::LessThan, left, right) = &exp.kind else {
+        panic!("expected a comparison");
+    };
+    assert_eq!(left.ty(), &Type::Long);
+    // The int operand is converted to the common type
+    assert_eq!(right.ty(), &Type::Long);
+    assert!(matches!(
+        &right.kind,
+        ExpressionKind::Cast(Type::Long, inner) if inner.ty() == &Type::Int
+    ));
+}
+
+#[test]
+fn test_implicit_conversions() {
+    let input = "long f(long x);\nint main(void) {\n int i = 10l;\n return f(i);\n}";
+    let program = analyze_source(input).unwrap();
+    let BlockItem::Declaration(Declaration::Variable(decl)) = &body(&program).0[0] else {
+        panic!("expected a declaration");
+    };
+    let Some(Initializer::Single(init)) = &decl.init else {
+        panic!("expected a single initializer");
+    };
+    assert!(matches!(&init.kind, ExpressionKind::Cast(Type::Int, _)));
+    // The argument is converted to long, and the result back to int
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[1] else {
+        panic!("expected a return statement");
+    };
+    let ExpressionKind::Cast(Type::Int, call) = &exp.kind else {
+        panic!("expected the return value to be converted");
+    };
+    let ExpressionKind::FunctionCall(_, args) = &call.kind else {
+        panic!("expected a call");
+    };
+    assert!(matches!(&args[0].kind, ExpressionKind::Cast(Type::Long, _)));
+}
+
+#[test]
+fn test_static_initializers_are_converted() {
+    let mut program = Parser::new("long a = 5; int b = 4294967297l;")
+        .parse_program()
+        .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    assert_eq!(
+        symbols.get("a").unwrap().attrs,
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::Long(5)]),
+            global: true,
+        }
+    );
+    assert_eq!(
+        symbols.get("b").unwrap().attrs,
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::Int(1)]),
+            global: true,
+        }
+    );
+}
+
+#[test]
+fn test_common_type_prefers_unsigned() {
+    let cases = [
+        ("int a; unsigned b;", Type::UInt),
+        ("long a; unsigned b;", Type::Long),
+        ("unsigned long a; long b;", Type::ULong),
+        ("int a; unsigned long b;", Type::ULong),
+    ];
+    for (decls, expected) in cases {
+        let source = format!("int main(void) {{ {} return (a + b) > 0; }}", decls);
+        let program = analyze_source(&source).unwrap();
+        let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[2] else {
+            panic!("expected a return statement");
+        };
+        let ExpressionKind::Binary(_, sum, _) = &exp.kind else {
+            panic!("expected a comparison");
+        };
+        assert_eq!(sum.ty(), &expected, "in {}", decls);
+    }
+}
+
+#[test]
+fn test_common_type_with_double() {
+    let program =
+        analyze_source("int main(void) { unsigned long a; double b; return a + b > 0; }").unwrap();
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[2] else {
+        panic!("expected a return statement");
+    };
+    let ExpressionKind::Binary(_, sum, zero) = &exp.kind else {
+        panic!("expected a comparison");
+    };
+    assert_eq!(sum.ty(), &Type::Double);
+    assert_eq!(zero.ty(), &Type::Double);
+    assert_eq!(exp.ty(), &Type::Int);
+}
+
+#[test]
+fn test_characters_are_promoted_to_int() {
+    let cases = [
+        ("c + 1", Type::Int),
+        ("c + u", Type::Int),
+        ("u * 2u", Type::UInt),
+        ("c + 1l", Type::Long),
+        ("-u", Type::Int),
+        ("~c", Type::Int),
+        ("c << 2l", Type::Int),
+        ("!c", Type::Int),
+    ];
+    for (exp, expected) in cases {
+        let source = format!(
+            "long main(void) {{ char c = 'A'; unsigned char u = 1; return {}; }}",
+            exp
+        );
+        let program = analyze_source(&source).unwrap();
+        let BlockItem::Statement(Statement::Return(ret)) = &body(&program).0[2] else {
+            panic!("expected a return statement");
+        };
+        // The return value is converted to long where needed
+        let value = match &ret.kind {
+            ExpressionKind::Cast(Type::Long, inner) if expected != Type::Long => inner,
+            _ => ret,
+        };
+        assert_eq!(value.ty(), &expected, "in {}", exp);
+    }
+    let program = analyze_source("int main(void) { char c = 'A'; return c + 1; }").unwrap();
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[1] else {
+        panic!("expected a return statement");
+    };
+    let ExpressionKind::Binary(BinaryOperator::Add, left, _) = &exp.kind else {
+        panic!("expected an addition");
+    };
+    assert!(matches!(
+        &left.kind,
+        ExpressionKind::Cast(Type::Int, inner) if inner.ty() == &Type::Char
+    ));
+}
+
+#[test]
+fn test_static_char_initializers() {
+    let mut program = Parser::new("char a = 'x'; unsigned char b = 300; signed char c[3] = {1};")
+        .parse_program()
+        .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    let init = |name| match &symbols.get(name).unwrap().attrs {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init.clone(),
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    assert_eq!(init("a"), vec![StaticInit::Char(120)]);
+    assert_eq!(init("b"), vec![StaticInit::UChar(44)]);
+    assert_eq!(init("c"), vec![StaticInit::Char(1), StaticInit::Zero(2)]);
+}
+
+#[test]
+fn test_integer_operators_reject_doubles() {
+    let err = semantic_error("int main(void) { double d = 1.0; return ~d; }");
+    assert_eq!(err, SemanticError::InvalidOperand("~", Type::Double, 1));
+    for op in ["%", "&", "|", "^", "<<", ">>"] {
+        let source = format!("int main(void) {{ double d = 1.0; return 3 {} d; }}", op);
+        let err = semantic_error(&source);
+        assert_eq!(
+            err.to_string(),
+            format!("invalid operand of type 'double' to '{}'", op)
+        );
+    }
+}
+
+#[test]
+fn test_pointer_types() {
+    let program =
+        analyze_source("int main(void) { long x; long *p = &x; return *p == 0 && p != 0; }")
+            .unwrap();
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[2] else {
+        panic!("expected a return statement");
+    };
+    let ExpressionKind::Binary(BinaryOperator::And, deref, compare) = &exp.kind else {
+        panic!("expected a logical and");
+    };
+    let ExpressionKind::Binary(_, value, _) = &deref.kind else {
+        panic!("expected a comparison");
+    };
+    assert_eq!(value.ty(), &Type::Long);
+    // The null pointer constant is converted to the pointer type
+    let ExpressionKind::Binary(_, _, null) = &compare.kind else {
+        panic!("expected a comparison");
+    };
+    let pointer = Type::Pointer(Box::new(Type::Long));
+    assert!(matches!(&null.kind, ExpressionKind::Cast(ty, _) if *ty == pointer));
+}
+
+#[test]
+fn test_incompatible_pointers() {
+    let cases = [
+        (
+            "int x; long *p = &x;",
+            "incompatible types 'int *' and 'long *'",
+        ),
+        ("int *p = 5;", "incompatible types 'int' and 'int *'"),
+        (
+            "int *p; long *q; p == q;",
+            "incompatible types 'int *' and 'long *'",
+        ),
+        (
+            "int *p; int **q; 1 ? p : q;",
+            "incompatible types 'int *' and 'int **'",
+        ),
+        ("int x = 1; *x;", "invalid operand of type 'int' to '*'"),
+        ("int *p; p * 2;", "invalid operand of type 'int *' to '*'"),
+        ("int *p; -p;", "invalid operand of type 'int *' to '-'"),
+        (
+            "double *p; (double) p;",
+            "cannot cast 'double *' to 'double'",
+        ),
+    ];
+    for (body, expected) in cases {
+        let source = format!("int main(void) {{ {} return 0; }}", body);
+        let err = semantic_error(&source);
+        assert_eq!(err.to_string(), expected, "in {}", body);
+    }
+    let err = semantic_error("long *f(void); int main(void) { int *p = f(); return 0; }");
+    assert_eq!(err.to_string(), "incompatible types 'long *' and 'int *'");
+}
+
+#[test]
+fn test_static_pointer_initializers() {
+    let mut program = Parser::new("int *p = 0; static double *q;")
+        .parse_program()
+        .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    assert_eq!(
+        symbols.get("p").unwrap().attrs,
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::ULong(0)]),
+            global: true,
+        }
+    );
+    let err = semantic_error("int *p = 1;");
+    assert_eq!(err.to_string(), "incompatible types 'int' and 'int *'");
+    // A constant expression that is zero is a null pointer constant too
+    let mut program = Parser::new("long *p = 2 - 2l;").parse_program().unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    assert_eq!(
+        symbols.get("p").unwrap().attrs,
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(vec![StaticInit::ULong(0)]),
+            global: true,
+        }
+    );
+    let err = semantic_error("int *p = 0.0 * 2;");
+    assert_eq!(err.to_string(), "incompatible types 'double' and 'int *'");
+    let err = semantic_error("int x; int *p = &x;");
+    assert_eq!(
+        err.to_string(),
+        "initializer for static variable 'p' is not constant"
+    );
+}
+
+#[test]
+fn test_arrays_decay_to_pointers() {
+    let program =
+        analyze_source("int main(void) { int a[3]; int *p = a; return *(a + 1); }").unwrap();
+    let BlockItem::Declaration(Declaration::Variable(p)) = &body(&program).0[1] else {
+        panic!("expected a declaration");
+    };
+    let Some(Initializer::Single(init)) = &p.init else {
+        panic!("expected a single initializer");
+    };
+    let int_pointer = Type::Pointer(Box::new(Type::Int));
+    assert_eq!(init.ty(), &int_pointer);
+    assert!(matches!(&init.kind, ExpressionKind::AddressOf(a)
+        if *a.ty() == Type::Array(Box::new(Type::Int), 3)));
+    // The integer added to a pointer is converted to long
+    let BlockItem::Statement(Statement::Return(exp)) = &body(&program).0[2] else {
+        panic!("expected a return statement");
+    };
+    let ExpressionKind::Dereference(sum) = &exp.kind else {
+        panic!("expected a dereference");
+    };
+    assert_eq!(sum.ty(), &int_pointer);
+    let ExpressionKind::Binary(BinaryOperator::Add, _, index) = &sum.kind else {
+        panic!("expected an addition");
+    };
+    assert_eq!(index.ty(), &Type::Long);
+}
+
+#[test]
+fn test_pointer_arithmetic_types() {
+    let cases = [
+        ("int *p; int *q; return p - q;", Type::Long),
+        ("int *p; int *q; return p <= q;", Type::Int),
+        ("long a[2][3]; return a[1][2];", Type::Long),
+        ("double a[2]; return 1[a];", Type::Double),
+    ];
+    for (statements, expected) in cases {
+        let source = format!("int main(void) {{ {} }}", statements);
+        let program = analyze_source(&source).unwrap();
+        let Some(BlockItem::Statement(Statement::Return(exp))) = body(&program).0.last() else {
+            panic!("expected a return statement");
+        };
+        let ExpressionKind::Cast(Type::Int, value) = &exp.kind else {
+            assert_eq!(expected, Type::Int, "in {}", statements);
+            continue;
+        };
+        assert_eq!(value.ty(), &expected, "in {}", statements);
+    }
+}
+
+#[test]
+fn test_array_type_errors() {
+    let cases = [
+        (
+            "int a[2]; int b[2]; a = b;",
+            "cannot assign to an expression of array type",
+        ),
+        (
+            "int *p; int *q; p + q;",
+            "invalid operand of type 'int *' to '+'",
+        ),
+        (
+            "int *p; p + 1.0;",
+            "invalid operand of type 'double' to '+'",
+        ),
+        (
+            "int *p; long *q; p < q;",
+            "incompatible types 'int *' and 'long *'",
+        ),
+        (
+            "int a[2]; long *p = a;",
+            "incompatible types 'int *' and 'long *'",
+        ),
+        ("int x; x[0];", "invalid operand of type 'int' to '[]'"),
+        (
+            "int a[2]; a[1.0];",
+            "invalid operand of type 'double' to '[]'",
+        ),
+        (
+            "int a[2]; (long) a[0] + (int[2]) a;",
+            "cannot cast 'int *' to 'int[2]'",
+        ),
+        (
+            "int a[2] = {1, 2, 3};",
+            "too many elements in initializer for 'int[2]'",
+        ),
+        ("int a[2] = 1;", "invalid initializer for type 'int[2]'"),
+        ("int x = {1};", "invalid initializer for type 'int'"),
+    ];
+    for (body, expected) in cases {
+        let source = format!("int main(void) {{ {} return 0; }}", body);
+        let err = semantic_error(&source);
+        assert_eq!(err.to_string(), expected, "in {}", body);
+    }
+}
+
+#[test]
+fn test_array_initializers_are_zero_filled() {
+    let program = analyze_source("int main(void) { long a[2][2] = {{1}}; return 0; }").unwrap();
+    let BlockItem::Declaration(Declaration::Variable(decl)) = &body(&program).0[0] else {
+        panic!("expected a declaration");
+    };
+    let Some(Initializer::Compound(rows, _)) = &decl.init else {
+        panic!("expected a compound initializer");
+    };
+    assert_eq!(rows.len(), 2);
+    let values: Vec<&ExpressionKind> = rows
+        .iter()
+        .flat_map(|row| match row {
+            Initializer::Compound(items, _) => items,
+            Initializer::Single(_) => panic!("expected a row"),
+        })
+        .map(|item| match item {
+            Initializer::Single(exp) => &exp.kind,
+            Initializer::Compound(..) => panic!("expected a scalar"),
+        })
+        .collect();
+    assert!(matches!(values[0], ExpressionKind::Cast(Type::Long, _)));
+    for value in &values[1..] {
+        assert_eq!(*value, &ExpressionKind::Constant(Const::Long(0)));
+    }
+}
+
+#[test]
+fn test_static_array_initializers() {
+    let mut program = Parser::new("int a[4] = {1, 2}; double b[2][2] = {{1}, {2, 3}}; long c[3];")
+        .parse_program()
+        .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    let init = |name| match &symbols.get(name).unwrap().attrs {
+        IdentifierAttrs::Static { init, .. } => init.clone(),
+        attrs => panic!("expected a static variable, got {:?}", attrs),
+    };
+    assert_eq!(
+        init("a"),
+        InitialValue::Initial(vec![
+            StaticInit::Int(1),
+            StaticInit::Int(2),
+            StaticInit::Zero(8)
+        ])
+    );
+    assert_eq!(
+        init("b"),
+        InitialValue::Initial(vec![
+            StaticInit::Double(1.0),
+            StaticInit::Zero(8),
+            StaticInit::Double(2.0),
+            StaticInit::Double(3.0),
+        ])
+    );
+    assert_eq!(init("c"), InitialValue::Tentative);
+    let err = semantic_error("int x; int a[2] = {1, x};");
+    assert_eq!(
+        err.to_string(),
+        "initializer for static variable 'a' is not constant"
+    );
+}
+
+#[test]
+fn test_string_literals_are_char_arrays() {
+    let mut program = Parser::new(r#"int main(void) { char *p = "abc"; return "de"[1]; }"#)
+        .parse_program()
+        .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    let BlockItem::Declaration(Declaration::Variable(decl)) = &body(&program).0[0] else {
+        panic!("expected a declaration");
+    };
+    // The literal is replaced by its label and decays to a pointer
+    let Some(Initializer::Single(init)) = &decl.init else {
+        panic!("expected a single initializer");
+    };
+    assert_eq!(init.ty(), &Type::Pointer(Box::new(Type::Char)));
+    let ExpressionKind::AddressOf(array) = &init.kind else {
+        panic!("expected the array to decay");
+    };
+    assert_eq!(array.kind, ExpressionKind::Var(".Lstring.0".to_string()));
+    assert_eq!(
+        symbols.get(".Lstring.0").unwrap(),
+        &Symbol {
+            ty: Type::Array(Box::new(Type::Char), 4),
+            attrs: IdentifierAttrs::Constant(StaticInit::String {
+                bytes: b"abc".to_vec(),
+                null_terminated: true,
+            }),
+        }
+    );
+    assert!(symbols.get(".Lstring.1").is_some());
+}
+
+#[test]
+fn test_string_initializers() {
+    let program =
+        analyze_source(r#"int main(void) { unsigned char s[5] = "abc"; return 0; }"#).unwrap();
+    let BlockItem::Declaration(Declaration::Variable(decl)) = &body(&program).0[0] else {
+        panic!("expected a declaration");
+    };
+    let Some(Initializer::Single(init)) = &decl.init else {
+        panic!("expected a single initializer");
+    };
+    // The array is initialized with the bytes themselves
+    assert_eq!(init.kind, ExpressionKind::String(b"abc".to_vec()));
+    assert_eq!(init.ty(), &Type::Array(Box::new(Type::UChar), 5));
+
+    let cases = [
+        (
+            r#"char s[2] = "abc";"#,
+            "initializer-string for 'char[2]' is too long",
+        ),
+        (
+            r#"int s[4] = "abc";"#,
+            "invalid initializer for type 'int[4]'",
+        ),
+        (
+            r#"char s[4] = 1;"#,
+            "invalid initializer for type 'char[4]'",
+        ),
+        (
+            r#"long *p = "abc";"#,
+            "incompatible types 'char *' and 'long *'",
+        ),
+    ];
+    for (decl, expected) in cases {
+        for source in [
+            format!("{} int main(void) {{ return 0; }}", decl),
+            format!("int main(void) {{ {} return 0; }}", decl),
+        ] {
+            assert_eq!(
+                semantic_error(&source).to_string(),
+                expected,
+                "in {}",
+                source
+            );
+        }
+    }
+    let err = semantic_error(r#"int main(void) { "abc" = 0; return 0; }"#);
+    assert_eq!(err, SemanticError::ArrayAssignment(1));
+}
+
+#[test]
+fn test_static_string_initializers() {
+    let mut program =
+        Parser::new(r#"char a[3] = "abc"; char b[6] = "ab"; char c[] = "xy"; char *p = "q";"#)
+            .parse_program()
+            .unwrap();
+    let symbols = analyze(&mut program).unwrap();
+    let init = |name| match &symbols.get(name).unwrap().attrs {
+        IdentifierAttrs::Static {
+            init: InitialValue::Initial(init),
+            ..
+        } => init.clone(),
+        attrs => panic!("expected an initialized static variable, got {:?}", attrs),
+    };
+    let string = |bytes: &[u8], null_terminated| StaticInit::String {
+        bytes: bytes.to_vec(),
+        null_terminated,
+    };
+    // There is no room for the null in a
+    assert_eq!(init("a"), vec![string(b"abc", false)]);
+    assert_eq!(init("b"), vec![string(b"ab", true), StaticInit::Zero(3)]);
+    assert_eq!(init("c"), vec![string(b"xy", true)]);
+    assert_eq!(
+        init("p"),
+        vec![StaticInit::Pointer(".Lstring.0".to_string())]
+    );
+    assert_eq!(
+        symbols.get(".Lstring.0").unwrap().attrs,
+        IdentifierAttrs::Constant(string(b"q", true))
+    );
+}
+
+#[test]
+fn test_extern_arrays_take_size_from_definition() {
+    let sources = [
+        "extern int a[]; int main(void) { return a[1]; } int a[3];",
+        "int a[3]; int main(void) { extern int a[]; return a[1]; }",
+        "int main(void) { extern int a[]; return a[1]; } extern int a[]; int a[] = {1, 2, 3};",
+    ];
+    for source in sources {
+        let mut program = Parser::new(source).parse_program().unwrap();
+        let symbols = analyze(&mut program).unwrap();
+        assert_eq!(
+            symbols.get("a").map(|symbol| &symbol.ty),
+            Some(&Type::Array(Box::new(Type::Int), 3)),
+            "in {}",
+            source
+        );
+    }
+    let e = semantic_error("extern int a[];\nlong a[3];");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("a".to_string(), 2));
+    let e = semantic_error("int a[2];\nint a[3];");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("a".to_string(), 2));
+    let e = semantic_error("extern int a[][2];\nint a[1][3];");
+    assert_eq!(e, SemanticError::ConflictingDeclaration("a".to_string(), 2));
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+#[test]
+fn test_variables_renamed() {
+    let input = r#"int main(void) {
+        int a = 1;
+        int b = a + 2;
+        return b;
+    }"#;
+    let program = analyze_source(input).unwrap();
+    let body = &body(&program).0;
+    let BlockItem::Declaration(Declaration::Variable(a)) = &body[0] else {
+        panic!("expected a declaration");
+    };
+    let BlockItem::Declaration(Declaration::Variable(b)) = &body[1] else {
+        panic!("expected a declaration");
+    };
+    assert_eq!(a.name, "a.0");
+    assert_eq!(b.name, "b.1");
+    let Some(Initializer::Single(Expression {
+        kind: ExpressionKind::Binary(_, left, _),
+        ..
+    })) = &b.init
+    else {
+        panic!("expected a binary initializer");
+    };
+    assert_eq!(left.kind, ExpressionKind::Var("a.0".to_string()));
+    assert_eq!(
+        body[2],
+        BlockItem::Statement(Statement::Return(Expression {
+            kind: ExpressionKind::Var("b.1".to_string()),
+            ty: Some(Type::Int),
+            line: 4,
+        }))
+    );
+}
+
+#[test]
+fn test_variable_in_own_initializer() {
+    let program = analyze_source("int main(void) { int a = a = 2; return a; }").unwrap();
+    let BlockItem::Declaration(Declaration::Variable(a)) = &body(&program).0[0] else {
+        panic!("expected a declaration");
+    };
+    let Some(Initializer::Single(Expression {
+        kind: ExpressionKind::Assignment(left, _),
+        ..
+    })) = &a.init
+    else {
+        panic!("expected an assignment");
+    };
+    assert_eq!(left.kind, ExpressionKind::Var("a.0".to_string()));
+}
+
+#[test]
+fn test_duplicate_declaration() {
+    let input = r#"int main(void) {
+        int a = 1;
+        int a;
+        return a;
+    }"#;
+    let err = semantic_error(input);
+    assert_eq!(err, SemanticError::DuplicateDeclaration("a".to_string(), 3));
+    assert_eq!(err.to_string(), "redeclaration of 'a'");
+}
+
+#[test]
+fn test_undeclared_variable() {
+    let input = r#"int main(void) {
+        int a = 1;
+        return a + b;
+    }"#;
+    let err = semantic_error(input);
+    assert_eq!(err, SemanticError::UndeclaredVariable("b".to_string(), 3));
+}
+
+#[test]
+fn test_use_before_declaration() {
+    let err = semantic_error("int main(void) { a = 1; int a; return a; }");
+    assert_eq!(err, SemanticError::UndeclaredVariable("a".to_string(), 1));
+}
+
+#[test]
+fn test_invalid_lvalue() {
+    let input = r#"int main(void) {
+        int x = 1;
+        2 = x;
+        return x;
+    }"#;
+    let err = semantic_error(input);
+    assert_eq!(err, SemanticError::InvalidLvalue(3));
+}
+
+#[test]
+fn test_invalid_compound_lvalue() {
+    let err = semantic_error("int main(void) { int a; int b; a + b = 3; return 0; }");
+    assert!(matches!(err, SemanticError::InvalidLvalue(1)));
+}
+
+#[test]
+fn test_conditional_is_not_lvalue() {
+    let err = semantic_error("int main(void) { int a; int b; 1 ? a : b = 3; return 0; }");
+    assert!(matches!(err, SemanticError::InvalidLvalue(1)));
+}
+
+#[test]
+fn test_dereference_is_lvalue() {
+    analyze_source("int main(void) { int a; int *p = &a; *p = 3; return a; }").unwrap();
+    let err = semantic_error("int main(void) { int a; int *p = &(a + 1); return 0; }");
+    assert_eq!(err, SemanticError::AddressOfRvalue(1));
+}
@@ -0,0 +1,888 @@
+use crate::parser::ast::*;
+use crate::semantic::constant::{constant_value, is_zero};
+use crate::semantic::resolve::source_name;
+use crate::semantic::symbols::*;
+use crate::semantic::SemanticError;
+
+/// Check that every identifier is used consistently with its type, and
+/// build the symbol table along the way. Every expression is annotated
+/// with its type, and implicit conversions are made explicit as casts.
+/// Names must already be resolved so that every local variable has a
+/// unique name.
+pub fn typecheck_program(program: &mut Program) -> Result<SymbolTable, SemanticError> {
+    let mut checker = TypeChecker::default();
+    for decl in &mut program.declarations {
+        match decl {
+            Declaration::Function(function) => checker.function_declaration(function)?,
+            Declaration::Variable(decl) => checker.file_scope_variable_declaration(decl)?,
+        }
+    }
+    Ok(checker.symbols)
+}
+
+#[derive(Default)]
+struct TypeChecker {
+    symbols: SymbolTable,
+    /// The return type of the function whose body is being checked
+    return_type: Option<Type>,
+}
+
+impl TypeChecker {
+    fn function_declaration(
+        &mut self,
+        function: &mut FunctionDeclaration,
+    ) -> Result<(), SemanticError> {
+        let has_body = function.body.is_some();
+        let mut already_defined = false;
+        let mut global = function.storage_class != Some(StorageClass::Static);
+        if let Some(prev) = self.symbols.get(&function.name) {
+            let IdentifierAttrs::Function {
+                defined,
+                global: prev_global,
+            } = prev.attrs
+            else {
+                return Err(SemanticError::ConflictingDeclaration(
+                    function.name.clone(),
+                    function.line,
+                ));
+            };
+            if prev.ty != function.fun_type {
+                return Err(SemanticError::ConflictingDeclaration(
+                    function.name.clone(),
+                    function.line,
+                ));
+            }
+            if defined && has_body {
+                return Err(SemanticError::Redefinition(
+                    function.name.clone(),
+                    function.line,
+                ));
+            }
+            // A static declaration cannot follow one with external
+            // linkage, but any later declaration inherits internal linkage
+            if prev_global && function.storage_class == Some(StorageClass::Static) {
+                return Err(SemanticError::ConflictingLinkage(
+                    function.name.clone(),
+                    function.line,
+                ));
+            }
+            already_defined = defined;
+            global = prev_global;
+        }
+        self.symbols.insert(
+            &function.name,
+            Symbol {
+                ty: function.fun_type.clone(),
+                attrs: IdentifierAttrs::Function {
+                    defined: already_defined || has_body,
+                    global,
+                },
+            },
+        );
+
+        let Some(body) = &mut function.body else {
+            return Ok(());
+        };
+        let Type::Function { params, ret } = &function.fun_type else {
+            unreachable!("function declarations have function types");
+        };
+        for (param, ty) in function.params.iter().zip(params) {
+            self.declare_local(param, ty.clone());
+        }
+        self.return_type = Some((**ret).clone());
+        self.block(body)
+    }
+
+    fn declare_local(&mut self, name: &str, ty: Type) {
+        self.symbols.insert(
+            name,
+            Symbol {
+                ty,
+                attrs: IdentifierAttrs::Local,
+            },
+        );
+    }
+
+    fn block(&mut self, block: &mut Block) -> Result<(), SemanticError> {
+        block.0.iter_mut().try_for_each(|item| match item {
+            BlockItem::Statement(stmt) => self.statement(stmt),
+            BlockItem::Declaration(Declaration::Variable(decl)) => self.variable_declaration(decl),
+            BlockItem::Declaration(Declaration::Function(function)) => {
+                self.function_declaration(function)
+            }
+        })
+    }
+
+    fn file_scope_variable_declaration(
+        &mut self,
+        decl: &VariableDeclaration,
+    ) -> Result<(), SemanticError> {
+        let mut init = match (&decl.init, decl.storage_class) {
+            (Some(init), _) => {
+                InitialValue::Initial(self.static_initializer(&decl.name, init, &decl.var_type)?)
+            }
+            (None, Some(StorageClass::Extern)) => InitialValue::NoInitializer,
+            (None, _) => InitialValue::Tentative,
+        };
+        let mut global = decl.storage_class != Some(StorageClass::Static);
+        let mut var_type = decl.var_type.clone();
+
+        if let Some(prev) = self.symbols.get(&decl.name) {
+            let IdentifierAttrs::Static {
+                init: prev_init,
+                global: prev_global,
+            } = &prev.attrs
+            else {
+                return Err(SemanticError::ConflictingDeclaration(
+                    decl.name.clone(),
+                    decl.line,
+                ));
+            };
+            let Some(ty) = composite_type(&prev.ty, &decl.var_type) else {
+                return Err(SemanticError::ConflictingDeclaration(
+                    decl.name.clone(),
+                    decl.line,
+                ));
+            };
+            var_type = ty;
+            // An extern declaration takes the linkage of a visible prior
+            // declaration, but otherwise the linkage must agree
+            if decl.storage_class == Some(StorageClass::Extern) {
+                global = *prev_global;
+            } else if *prev_global != global {
+                return Err(SemanticError::ConflictingLinkage(
+                    decl.name.clone(),
+                    decl.line,
+                ));
+            }
+            init = match (prev_init, init) {
+                (InitialValue::Initial(_), InitialValue::Initial(_)) => {
+                    return Err(SemanticError::Redefinition(decl.name.clone(), decl.line))
+                }
+                (InitialValue::Initial(_), _) => prev_init.clone(),
+                (InitialValue::Tentative, InitialValue::NoInitializer) => InitialValue::Tentative,
+                (_, init) => init,
+            };
+        }
+
+        self.symbols.insert(
+            &decl.name,
+            Symbol {
+                ty: var_type,
+                attrs: IdentifierAttrs::Static { init, global },
+            },
+        );
+        Ok(())
+    }
+
+    fn variable_declaration(
+        &mut self,
+        decl: &mut VariableDeclaration,
+    ) -> Result<(), SemanticError> {
+        match decl.storage_class {
+            Some(StorageClass::Extern) => {
+                if decl.init.is_some() {
+                    return Err(SemanticError::ExternWithInitializer(
+                        decl.name.clone(),
+                        decl.line,
+                    ));
+                }
+                match self.symbols.get(&decl.name) {
+                    Some(prev) if composite_type(&prev.ty, &decl.var_type).is_none() => {
+                        return Err(SemanticError::ConflictingDeclaration(
+                            decl.name.clone(),
+                            decl.line,
+                        ))
+                    }
+                    Some(_) => {}
+                    None => self.symbols.insert(
+                        &decl.name,
+                        Symbol {
+                            ty: decl.var_type.clone(),
+                            attrs: IdentifierAttrs::Static {
+                                init: InitialValue::NoInitializer,
+                                global: true,
+                            },
+                        },
+                    ),
+                }
+                Ok(())
+            }
+            Some(StorageClass::Static) => {
+                let init = match &decl.init {
+                    Some(init) => self.static_initializer(&decl.name, init, &decl.var_type)?,
+                    None => vec![StaticInit::zero(&decl.var_type)],
+                };
+                self.symbols.insert(
+                    &decl.name,
+                    Symbol {
+                        ty: decl.var_type.clone(),
+                        attrs: IdentifierAttrs::Static {
+                            init: InitialValue::Initial(init),
+                            global: false,
+                        },
+                    },
+                );
+                Ok(())
+            }
+            None => {
+                self.declare_local(&decl.name, decl.var_type.clone());
+                if let Some(init) = &mut decl.init {
+                    self.initializer(init, &decl.var_type)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Check the initializer of a local variable of type `ty`. Elements
+    /// of an array without an initializer are filled in with zeros.
+    fn initializer(&mut self, init: &mut Initializer, ty: &Type) -> Result<(), SemanticError> {
+        match (init, ty) {
+            (Initializer::Single(exp), _) if !ty.is_array() => {
+                self.expression(exp)?;
+                convert_by_assignment(exp, ty)
+            }
+            (Initializer::Compound(items, line), Type::Array(element, count)) => {
+                if items.len() > *count {
+                    return Err(SemanticError::ExcessElements(ty.clone(), *line));
+                }
+                for item in items.iter_mut() {
+                    self.initializer(item, element)?;
+                }
+                items.resize(*count, zero_initializer(element, *line));
+                Ok(())
+            }
+            // A string literal initializes an array of characters with
+            // its bytes, which are not converted to a pointer
+            (Initializer::Single(exp), Type::Array(element, count)) if element.is_character() => {
+                match &exp.kind {
+                    ExpressionKind::String(bytes) if bytes.len() > *count => {
+                        Err(SemanticError::StringTooLong(ty.clone(), exp.line))
+                    }
+                    ExpressionKind::String(_) => {
+                        exp.ty = Some(ty.clone());
+                        Ok(())
+                    }
+                    _ => Err(SemanticError::InvalidInitializer(ty.clone(), exp.line)),
+                }
+            }
+            (init, _) => Err(SemanticError::InvalidInitializer(
+                ty.clone(),
+                initializer_line(init),
+            )),
+        }
+    }
+
+    /// The values that initialize the variable `name` of type `ty` with
+    /// static storage duration, in the order they are laid out in
+    /// memory. Elements of an array without an initializer are zero.
+    fn static_initializer(
+        &mut self,
+        name: &str,
+        init: &Initializer,
+        ty: &Type,
+    ) -> Result<Vec<StaticInit>, SemanticError> {
+        match (init, ty) {
+            (Initializer::Single(exp), _) if !ty.is_array() => {
+                Ok(vec![self.constant_initializer(name, exp, ty)?])
+            }
+            (Initializer::Compound(items, line), Type::Array(element, count)) => {
+                if items.len() > *count {
+                    return Err(SemanticError::ExcessElements(ty.clone(), *line));
+                }
+                let mut values = Vec::new();
+                for item in items {
+                    values.extend(self.static_initializer(name, item, element)?);
+                }
+                if items.len() < *count {
+                    values.push(StaticInit::Zero((count - items.len()) * element.size()));
+                }
+                Ok(values)
+            }
+            (Initializer::Single(exp), Type::Array(element, count)) if element.is_character() => {
+                match &exp.kind {
+                    ExpressionKind::String(bytes) if bytes.len() > *count => {
+                        Err(SemanticError::StringTooLong(ty.clone(), exp.line))
+                    }
+                    ExpressionKind::String(bytes) => {
+                        let mut values = vec![StaticInit::String {
+                            bytes: bytes.clone(),
+                            null_terminated: bytes.len() < *count,
+                        }];
+                        if bytes.len() + 1 < *count {
+                            values.push(StaticInit::Zero(count - bytes.len() - 1));
+                        }
+                        Ok(values)
+                    }
+                    _ => Err(SemanticError::InvalidInitializer(ty.clone(), exp.line)),
+                }
+            }
+            (init, _) => Err(SemanticError::InvalidInitializer(
+                ty.clone(),
+                initializer_line(init),
+            )),
+        }
+    }
+
+    /// The value of a scalar initializer of a variable with static
+    /// storage duration, converted to the type of the variable. It is
+    /// set before the program starts and so must be a constant.
+    fn constant_initializer(
+        &mut self,
+        name: &str,
+        init: &Expression,
+        ty: &Type,
+    ) -> Result<StaticInit, SemanticError> {
+        if let ExpressionKind::String(bytes) = &init.kind {
+            // A string literal initializes a char pointer with its address
+            let char_pointer = Type::Pointer(Box::new(Type::Char));
+            if *ty != char_pointer {
+                return Err(SemanticError::IncompatibleTypes(
+                    char_pointer,
+                    ty.clone(),
+                    init.line,
+                ));
+            }
+            return Ok(StaticInit::Pointer(self.symbols.add_string(bytes)));
+        }
+        let Some(value) = constant_value(init) else {
+            return Err(SemanticError::NonConstantInitializer(
+                source_name(name).to_string(),
+                init.line,
+            ));
+        };
+        // The only other constant a pointer can be initialized with is null
+        if ty.is_pointer() && !(is_integer(&value.ty()) && is_zero(value)) {
+            return Err(SemanticError::IncompatibleTypes(
+                value.ty(),
+                ty.clone(),
+                init.line,
+            ));
+        }
+        Ok(StaticInit::from_const(value, ty))
+    }
+
+    fn statement(&mut self, stmt: &mut Statement) -> Result<(), SemanticError> {
+        match stmt {
+            Statement::Return(exp) => {
+                self.expression(exp)?;
+                let ty = self
+                    .return_type
+                    .clone()
+                    .expect("return statements are inside functions");
+                convert_by_assignment(exp, &ty)
+            }
+            Statement::Expression(exp) => self.expression(exp),
+            Statement::If(condition, then, otherwise) => {
+                self.expression(condition)?;
+                self.statement(then)?;
+                match otherwise {
+                    Some(otherwise) => self.statement(otherwise),
+                    None => Ok(()),
+                }
+            }
+            Statement::Compound(block) => self.block(block),
+            Statement::While {
+                condition, body, ..
+            }
+            | Statement::DoWhile {
+                body, condition, ..
+            } => {
+                self.expression(condition)?;
+                self.statement(body)
+            }
+            Statement::For {
+                init,
+                condition,
+                post,
+                body,
+                ..
+            } => {
+                match init.as_mut() {
+                    ForInit::Declaration(decl) => self.variable_declaration(decl)?,
+                    ForInit::Expression(Some(exp)) => self.expression(exp)?,
+                    ForInit::Expression(None) => {}
+                }
+                for exp in [condition, post].into_iter().flatten() {
+                    self.expression(exp)?;
+                }
+                self.statement(body)
+            }
+            Statement::Break { .. } | Statement::Continue { .. } | Statement::Null => Ok(()),
+        }
+    }
+
+    /// Check an expression and record its type. An array is converted
+    /// to a pointer to its first element, except where it is the operand
+    /// of '&' or the target of an assignment.
+    fn expression(&mut self, exp: &mut Expression) -> Result<(), SemanticError> {
+        self.expression_without_decay(exp)?;
+        if let Type::Array(element, _) = exp.ty() {
+            let ty = Type::Pointer(element.clone());
+            let line = exp.line;
+            let inner = std::mem::replace(
+                exp,
+                Expression::new(ExpressionKind::Constant(Const::Int(0)), line),
+            );
+            *exp = Expression {
+                kind: ExpressionKind::AddressOf(Box::new(inner)),
+                ty: Some(ty),
+                line,
+            };
+        }
+        Ok(())
+    }
+
+    fn expression_without_decay(&mut self, exp: &mut Expression) -> Result<(), SemanticError> {
+        // A string literal is an array of char in read-only memory, which
+        // is referred to by its label from here on
+        if let ExpressionKind::String(bytes) = &exp.kind {
+            exp.kind = ExpressionKind::Var(self.symbols.add_string(bytes));
+        }
+        let ty = match &mut exp.kind {
+            ExpressionKind::Constant(value) => value.ty(),
+            ExpressionKind::String(_) => unreachable!("string literals are replaced above"),
+            ExpressionKind::Var(name) => match &self.symbol(name).ty {
+                Type::Function { .. } => {
+                    return Err(SemanticError::FunctionUsedAsVariable(
+                        name.clone(),
+                        exp.line,
+                    ))
+                }
+                ty => ty.clone(),
+            },
+            ExpressionKind::Cast(ty, operand) => {
+                self.expression(operand)?;
+                let from = operand.ty();
+                if (from.is_pointer() && *ty == Type::Double)
+                    || (*from == Type::Double && ty.is_pointer())
+                    || ty.is_array()
+                {
+                    return Err(SemanticError::InvalidCast(
+                        from.clone(),
+                        ty.clone(),
+                        exp.line,
+                    ));
+                }
+                ty.clone()
+            }
+            ExpressionKind::Unary(op, operand) => {
+                self.expression(operand)?;
+                if *op != UnaryOperator::Not {
+                    promote(operand);
+                }
+                let ty = operand.ty();
+                match op {
+                    UnaryOperator::Not => Type::Int,
+                    UnaryOperator::Negate if ty.is_arithmetic() => ty.clone(),
+                    UnaryOperator::Complement if is_integer(ty) => ty.clone(),
+                    UnaryOperator::Negate => {
+                        return Err(SemanticError::InvalidOperand("-", ty.clone(), exp.line))
+                    }
+                    UnaryOperator::Complement => {
+                        return Err(SemanticError::InvalidOperand("~", ty.clone(), exp.line))
+                    }
+                }
+            }
+            ExpressionKind::Dereference(operand) => {
+                self.expression(operand)?;
+                match operand.ty() {
+                    Type::Pointer(referenced) => (**referenced).clone(),
+                    ty => return Err(SemanticError::InvalidOperand("*", ty.clone(), exp.line)),
+                }
+            }
+            ExpressionKind::AddressOf(operand) => {
+                self.expression_without_decay(operand)?;
+                Type::Pointer(Box::new(operand.ty().clone()))
+            }
+            ExpressionKind::Subscript(left, right) => {
+                self.expression(left)?;
+                self.expression(right)?;
+                // Either operand can be the pointer: a[1] is 1[a]
+                let (pointer, index) = if left.ty().is_pointer() {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                let Type::Pointer(referenced) = pointer.ty().clone() else {
+                    return Err(SemanticError::InvalidOperand(
+                        "[]",
+                        pointer.ty().clone(),
+                        exp.line,
+                    ));
+                };
+                if !is_integer(index.ty()) {
+                    return Err(SemanticError::InvalidOperand(
+                        "[]",
+                        index.ty().clone(),
+                        exp.line,
+                    ));
+                }
+                convert_to(index, &Type::Long);
+                *referenced
+            }
+            ExpressionKind::Binary(op, left, right) => {
+                self.expression(left)?;
+                self.expression(right)?;
+                binary(*op, left, right, exp.line)?
+            }
+            ExpressionKind::Assignment(left, right) => {
+                self.expression_without_decay(left)?;
+                if left.ty().is_array() {
+                    return Err(SemanticError::ArrayAssignment(exp.line));
+                }
+                self.expression(right)?;
+                let ty = left.ty().clone();
+                convert_by_assignment(right, &ty)?;
+                ty
+            }
+            ExpressionKind::Conditional(condition, then, otherwise) => {
+                self.expression(condition)?;
+                self.expression(then)?;
+                self.expression(otherwise)?;
+                let common = if then.ty().is_pointer() || otherwise.ty().is_pointer() {
+                    common_pointer_type(then, otherwise)?
+                } else {
+                    common_type(then.ty(), otherwise.ty())
+                };
+                convert_to(then, &common);
+                convert_to(otherwise, &common);
+                common
+            }
+            ExpressionKind::FunctionCall(name, args) => {
+                let Type::Function { params, ret } = self.symbol(name).ty.clone() else {
+                    return Err(SemanticError::VariableUsedAsFunction(
+                        source_name(name).to_string(),
+                        exp.line,
+                    ));
+                };
+                if params.len() != args.len() {
+                    return Err(SemanticError::WrongArgumentCount {
+                        name: name.clone(),
+                        expected: params.len(),
+                        found: args.len(),
+                        line: exp.line,
+                    });
+                }
+                for (arg, ty) in args.iter_mut().zip(&params) {
+                    self.expression(arg)?;
+                    convert_by_assignment(arg, ty)?;
+                }
+                *ret
+            }
+        };
+        exp.ty = Some(ty);
+        Ok(())
+    }
+
+    fn symbol(&self, name: &str) -> &Symbol {
+        self.symbols
+            .get(name)
+            .expect("identifiers are resolved before type checking")
+    }
+}
+
+/// Check the typed operands of a binary operator, convert them to a
+/// common type where needed and return the type of the result
+fn binary(
+    op: BinaryOperator,
+    left: &mut Expression,
+    right: &mut Expression,
+    line: usize,
+) -> Result<Type, SemanticError> {
+    if left.ty().is_pointer() || right.ty().is_pointer() {
+        return pointer_binary(op, left, right, line);
+    }
+    if let Some(operand) = [&left, &right]
+        .into_iter()
+        .find(|operand| !accepts_operand(op, operand.ty()))
+    {
+        return Err(SemanticError::InvalidOperand(
+            spelling(op),
+            operand.ty().clone(),
+            line,
+        ));
+    }
+    Ok(match op {
+        BinaryOperator::And | BinaryOperator::Or => Type::Int,
+        // The operands of a shift are promoted separately, and the
+        // result has the type of the left one
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => {
+            promote(left);
+            promote(right);
+            left.ty().clone()
+        }
+        _ => {
+            let common = common_type(left.ty(), right.ty());
+            convert_to(left, &common);
+            convert_to(right, &common);
+            if is_comparison(op) {
+                Type::Int
+            } else {
+                common
+            }
+        }
+    })
+}
+
+/// Check a binary operator with at least one pointer operand. Pointers
+/// can be compared for equality with pointers of the same type and with
+/// null pointer constants. Pointers of the same type can also be
+/// ordered and subtracted, and an integer can be added to or subtracted
+/// from a pointer, in which case it is converted to long.
+fn pointer_binary(
+    op: BinaryOperator,
+    left: &mut Expression,
+    right: &mut Expression,
+    line: usize,
+) -> Result<Type, SemanticError> {
+    let both_pointers = left.ty().is_pointer() && right.ty().is_pointer();
+    match op {
+        BinaryOperator::And | BinaryOperator::Or => Ok(Type::Int),
+        BinaryOperator::Equal | BinaryOperator::NotEqual => {
+            let common = common_pointer_type(left, right)?;
+            convert_to(left, &common);
+            convert_to(right, &common);
+            Ok(Type::Int)
+        }
+        BinaryOperator::Add | BinaryOperator::Subtract
+            if left.ty().is_pointer() && is_integer(right.ty()) =>
+        {
+            convert_to(right, &Type::Long);
+            Ok(left.ty().clone())
+        }
+        BinaryOperator::Add if is_integer(left.ty()) => {
+            convert_to(left, &Type::Long);
+            Ok(right.ty().clone())
+        }
+        BinaryOperator::Subtract
+        | BinaryOperator::LessThan
+        | BinaryOperator::LessOrEqual
+        | BinaryOperator::GreaterThan
+        | BinaryOperator::GreaterOrEqual
+            if both_pointers =>
+        {
+            if left.ty() != right.ty() {
+                return Err(SemanticError::IncompatibleTypes(
+                    left.ty().clone(),
+                    right.ty().clone(),
+                    line,
+                ));
+            }
+            Ok(if op == BinaryOperator::Subtract {
+                Type::Long
+            } else {
+                Type::Int
+            })
+        }
+        _ => {
+            // Report the operand that cannot go with a pointer, or the
+            // pointer itself if the operator does not take pointers
+            let is_additive = matches!(op, BinaryOperator::Add | BinaryOperator::Subtract);
+            let operand = if is_additive == left.ty().is_pointer() {
+                right
+            } else {
+                left
+            };
+            Err(SemanticError::InvalidOperand(
+                spelling(op),
+                operand.ty().clone(),
+                line,
+            ))
+        }
+    }
+}
+
+/// Whether a binary operator can be applied to an operand of type `ty`.
+/// The logical operators take any scalar, the bitwise operators, shifts
+/// and remainder only integers, and the rest any arithmetic type.
+fn accepts_operand(op: BinaryOperator, ty: &Type) -> bool {
+    match op {
+        BinaryOperator::And | BinaryOperator::Or => true,
+        BinaryOperator::Remainder
+        | BinaryOperator::BitwiseAnd
+        | BinaryOperator::BitwiseOr
+        | BinaryOperator::BitwiseXor
+        | BinaryOperator::ShiftLeft
+        | BinaryOperator::ShiftRight => is_integer(ty),
+        _ => ty.is_arithmetic(),
+    }
+}
+
+/// The type of a variable declared with both types, or None if they
+/// are not compatible. An array of unknown size takes the size of the
+/// other declaration.
+fn composite_type(prev: &Type, ty: &Type) -> Option<Type> {
+    match (prev, ty) {
+        _ if prev == ty => Some(prev.clone()),
+        (Type::Array(prev_element, prev_count), Type::Array(element, count))
+            if prev_element == element && (*prev_count == 0 || *count == 0) =>
+        {
+            Some(Type::Array(element.clone(), *prev_count.max(count)))
+        }
+        _ => None,
+    }
+}
+
+fn is_integer(ty: &Type) -> bool {
+    ty.is_arithmetic() && *ty != Type::Double
+}
+
+/// The type of an operand after the integer promotions, which convert
+/// the character types to int
+pub(super) fn promoted(ty: &Type) -> Type {
+    if ty.is_character() {
+        Type::Int
+    } else {
+        ty.clone()
+    }
+}
+
+fn promote(exp: &mut Expression) {
+    let ty = promoted(exp.ty());
+    convert_to(exp, &ty);
+}
+
+/// The type both operands are converted to by the usual arithmetic
+/// conversions: double if either is double, otherwise the larger of the
+/// promoted types, or the unsigned one if both have the same size
+pub(super) fn common_type(left: &Type, right: &Type) -> Type {
+    let (left, right) = (&promoted(left), &promoted(right));
+    if *left == Type::Double || *right == Type::Double {
+        Type::Double
+    } else if left == right {
+        left.clone()
+    } else if left.size() == right.size() {
+        if left.is_signed() {
+            right.clone()
+        } else {
+            left.clone()
+        }
+    } else if left.size() > right.size() {
+        left.clone()
+    } else {
+        right.clone()
+    }
+}
+
+/// The type that two operands are converted to when at least one of
+/// them is a pointer: either both have the same type or one of them is
+/// a null pointer constant
+fn common_pointer_type(left: &Expression, right: &Expression) -> Result<Type, SemanticError> {
+    if left.ty() == right.ty() || is_null_pointer_constant(right) {
+        Ok(left.ty().clone())
+    } else if is_null_pointer_constant(left) {
+        Ok(right.ty().clone())
+    } else {
+        Err(SemanticError::IncompatibleTypes(
+            left.ty().clone(),
+            right.ty().clone(),
+            left.line,
+        ))
+    }
+}
+
+/// An integer constant with the value zero, which converts implicitly
+/// to any pointer type
+fn is_null_pointer_constant(exp: &Expression) -> bool {
+    matches!(
+        exp.kind,
+        ExpressionKind::Constant(Const::Int(0) | Const::Long(0) | Const::UInt(0) | Const::ULong(0))
+    )
+}
+
+fn spelling(op: BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Remainder => "%",
+        BinaryOperator::BitwiseAnd => "&",
+        BinaryOperator::BitwiseOr => "|",
+        BinaryOperator::BitwiseXor => "^",
+        BinaryOperator::ShiftLeft => "<<",
+        BinaryOperator::ShiftRight => ">>",
+        BinaryOperator::And => "&&",
+        BinaryOperator::Or => "||",
+        BinaryOperator::Equal => "==",
+        BinaryOperator::NotEqual => "!=",
+        BinaryOperator::LessThan => "<",
+        BinaryOperator::LessOrEqual => "<=",
+        BinaryOperator::GreaterThan => ">",
+        BinaryOperator::GreaterOrEqual => ">=",
+    }
+}
+
+pub(super) fn is_comparison(op: BinaryOperator) -> bool {
+    matches!(
+        op,
+        BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterOrEqual
+    )
+}
+
+/// Convert an expression to `ty` as if by assignment. Arithmetic types
+/// convert to each other, but a pointer only accepts a pointer of the
+/// same type or a null pointer constant.
+fn convert_by_assignment(exp: &mut Expression, ty: &Type) -> Result<(), SemanticError> {
+    let from = exp.ty();
+    if from == ty
+        || (from.is_arithmetic() && ty.is_arithmetic())
+        || (ty.is_pointer() && is_null_pointer_constant(exp))
+    {
+        convert_to(exp, ty);
+        Ok(())
+    } else {
+        Err(SemanticError::IncompatibleTypes(
+            from.clone(),
+            ty.clone(),
+            exp.line,
+        ))
+    }
+}
+
+/// Wrap a typed expression in a cast to `ty`, unless it already has
+/// that type
+fn convert_to(exp: &mut Expression, ty: &Type) {
+    if exp.ty() == ty {
+        return;
+    }
+    let line = exp.line;
+    let inner = std::mem::replace(
+        exp,
+        Expression::new(ExpressionKind::Constant(Const::Int(0)), line),
+    );
+    *exp = Expression {
+        kind: ExpressionKind::Cast(ty.clone(), Box::new(inner)),
+        ty: Some(ty.clone()),
+        line,
+    };
+}
+
+/// An initializer that sets every scalar in an object of type `ty` to
+/// zero
+fn zero_initializer(ty: &Type, line: usize) -> Initializer {
+    match ty {
+        Type::Array(element, count) => {
+            Initializer::Compound(vec![zero_initializer(element, line); *count], line)
+        }
+        _ => Initializer::Single(Expression {
+            kind: ExpressionKind::Constant(Const::Int(0).convert_to(ty)),
+            ty: Some(ty.clone()),
+            line,
+        }),
+    }
+}
+
+fn initializer_line(init: &Initializer) -> usize {
+    match init {
+        Initializer::Single(exp) => exp.line,
+        Initializer::Compound(_, line) => *line,
+    }
+}